@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
+#![allow(clippy::cast_lossless)]
 #![feature(test)]
 #![allow(non_snake_case)]
 
@@ -48,6 +48,21 @@ macro_rules! benches {
             )*
         }
 
+        mod bench_itoa_write_forward {
+            use test::{Bencher, black_box};
+            $(
+                $(#[$attr])*
+                #[bench]
+                fn $name(b: &mut Bencher) {
+                    use itoa;
+
+                    let mut buf = [0u8; 40];
+
+                    b.iter(|| itoa::write_forward(&mut buf, black_box($value)).unwrap());
+                }
+            )*
+        }
+
         mod bench_std_fmt {
             use test::{Bencher, black_box};
             $(
@@ -81,3 +96,29 @@ benches! {
     #[cfg(feature = "i128")]
     bench_u128_max(<u128>::max_value())
 }
+
+#[cfg(feature = "alloc")]
+mod bench_format_column {
+    use test::{black_box, Bencher};
+
+    fn column() -> Vec<i64> {
+        (0..1000i64).map(|i| i * i * i - 500_000_000).collect()
+    }
+
+    #[bench]
+    fn bench_itoa_format_column(b: &mut Bencher) {
+        let values = column();
+        b.iter(|| itoa::format_column(black_box(&values)));
+    }
+
+    #[bench]
+    fn bench_std_to_string(b: &mut Bencher) {
+        let values = column();
+        b.iter(|| {
+            black_box(&values)
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<String>>()
+        });
+    }
+}
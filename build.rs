@@ -0,0 +1,79 @@
+use std::env;
+use std::process::Command;
+use std::str;
+
+// The rustc-cfg strings below are *not* public API. Please let us know by
+// opening a GitHub issue if your build environment requires some way to enable
+// these cfgs other than by executing our build script.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let minor = match rustc_minor_version() {
+        Some(minor) => minor,
+        None => return,
+    };
+
+    if minor >= 80 {
+        println!("cargo:rustc-check-cfg=cfg(no_aarch64_neon)");
+        println!("cargo:rustc-check-cfg=cfg(no_const_fn_trait_bound)");
+        println!("cargo:rustc-check-cfg=cfg(no_const_generics)");
+        println!("cargo:rustc-check-cfg=cfg(no_const_loop)");
+        println!("cargo:rustc-check-cfg=cfg(no_core_num_saturating)");
+        println!("cargo:rustc-check-cfg=cfg(no_maybe_uninit_write_slice)");
+        println!("cargo:rustc-check-cfg=cfg(no_target_has_atomic)");
+        println!("cargo:rustc-check-cfg=cfg(target_pointer_width, values(\"128\"))");
+    }
+
+    // Branches and loops in const fn stabilized in Rust 1.46:
+    // https://blog.rust-lang.org/2020/08/27/Rust-1.46.0.html#const-fn-improvements
+    if minor < 46 {
+        println!("cargo:rustc-cfg=no_const_loop");
+    }
+
+    // AArch64 NEON intrinsics stabilized in Rust 1.59:
+    // https://blog.rust-lang.org/2022/02/24/Rust-1.59.0.html#stabilized-apis
+    if minor < 59 {
+        println!("cargo:rustc-cfg=no_aarch64_neon");
+    }
+
+    // Trait bounds on const fn parameters stabilized in Rust 1.61:
+    // https://blog.rust-lang.org/2022/05/19/Rust-1.61.0.html#more-capabilities-for-const-fn
+    if minor < 61 {
+        println!("cargo:rustc-cfg=no_const_fn_trait_bound");
+    }
+
+    // Saturating<T> stabilized in Rust 1.74:
+    // https://blog.rust-lang.org/2023/11/16/Rust-1.74.0.html#stabilized-apis
+    if minor < 74 {
+        println!("cargo:rustc-cfg=no_core_num_saturating");
+    }
+
+    // <[MaybeUninit<T>]>::write_copy_of_slice stabilized in Rust 1.93:
+    // https://doc.rust-lang.org/std/primitive.slice.html#method.write_copy_of_slice
+    if minor < 93 {
+        println!("cargo:rustc-cfg=no_maybe_uninit_write_slice");
+    }
+
+    // cfg(target_has_atomic) stabilized in Rust 1.60:
+    // https://blog.rust-lang.org/2022/04/07/Rust-1.60.0.html#stabilized-apis
+    if minor < 60 {
+        println!("cargo:rustc-cfg=no_target_has_atomic");
+    }
+
+    // Const generics stabilized in Rust 1.51:
+    // https://blog.rust-lang.org/2021/03/25/Rust-1.51.0.html#const-generics-mvp
+    if minor < 51 {
+        println!("cargo:rustc-cfg=no_const_generics");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = str::from_utf8(&output.stdout).ok()?;
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+    pieces.next()?.parse().ok()
+}
@@ -0,0 +1,16 @@
+use arrayvec::{ArrayString, CapacityError};
+use {Buffer, Integer};
+
+/// Append integer to an `arrayvec::ArrayString<N>`.
+///
+/// Returns an error without modifying the string if the formatted integer
+/// does not fit in its remaining capacity.
+#[inline]
+pub fn push_arraystring<const N: usize, V: Integer>(
+    s: &mut ArrayString<N>,
+    value: V,
+) -> Result<(), CapacityError> {
+    let mut buf = Buffer::new();
+    s.try_push_str(buf.format(value))
+        .map_err(CapacityError::simplify)
+}
@@ -0,0 +1,40 @@
+// Kept out of lib.rs because compilers older than 1.60 reject
+// cfg(target_has_atomic) wherever it appears, even in inactive code.
+
+#[cfg(not(feature = "std"))]
+use core::mem::MaybeUninit;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{self, Ordering};
+#[cfg(feature = "std")]
+use std::mem::MaybeUninit;
+#[cfg(feature = "std")]
+use std::sync::atomic::{self, Ordering};
+use {private, BufferByte, Integer};
+
+// References to atomics format the value of a single load with Relaxed
+// ordering, which is enough for diagnostics but does not synchronize with
+// other memory accesses.
+macro_rules! impl_Atomic {
+    ($($t:ident($bits:tt) => $inner:ident),*) => {$(
+        #[cfg(target_has_atomic = $bits)]
+        impl Integer for &atomic::$t {
+            delegate_Integer!($inner, |n| n.load(Ordering::Relaxed));
+        }
+
+        #[cfg(target_has_atomic = $bits)]
+        impl private::Sealed for &atomic::$t {}
+    )*};
+}
+
+impl_Atomic!(
+    AtomicI8("8") => i8,
+    AtomicU8("8") => u8,
+    AtomicI16("16") => i16,
+    AtomicU16("16") => u16,
+    AtomicI32("32") => i32,
+    AtomicU32("32") => u32,
+    AtomicI64("64") => i64,
+    AtomicU64("64") => u64,
+    AtomicIsize("ptr") => isize,
+    AtomicUsize("ptr") => usize
+);
@@ -0,0 +1,79 @@
+// Kept out of lib.rs so that the exported symbol only exists when the `ffi`
+// feature asks for it.
+
+#[cfg(not(feature = "std"))]
+use core::ptr;
+#[cfg(feature = "std")]
+use std::ptr;
+use {Integer, MAX_LEN};
+
+/// Scratch space for [`itoa_format_u64`], with a layout that is stable for
+/// FFI.
+///
+/// The struct is `#[repr(C)]` with a single field, so in C it is equivalent
+/// to `struct { uint8_t bytes[41]; }`. That is room for the longest integer
+/// of any type plus a terminating NUL.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct FfiBuffer {
+    /// Output of the most recent call, starting at the first byte and
+    /// terminated by a NUL.
+    pub bytes: [u8; MAX_LEN + 1],
+}
+
+impl FfiBuffer {
+    /// Zero-initialized scratch space.
+    #[inline]
+    pub const fn new() -> Self {
+        FfiBuffer {
+            bytes: [0; MAX_LEN + 1],
+        }
+    }
+}
+
+impl Default for FfiBuffer {
+    #[inline]
+    fn default() -> Self {
+        FfiBuffer::new()
+    }
+}
+
+/// Format a `u64` into `buf` as a NUL-terminated string, for calling from C.
+///
+/// Returns a pointer to the first byte of `buf`. The number of digits, not
+/// counting the NUL, is stored to `len` unless `len` is null. If `buf` is
+/// null, nothing is formatted, a length of 0 is stored to `len` unless `len`
+/// is null, and a null pointer is returned.
+///
+/// ```
+/// let mut buf = itoa::FfiBuffer::new();
+/// let mut len = 0;
+/// let ptr = unsafe { itoa::itoa_format_u64(&mut buf, 1234, &mut len) };
+/// assert_eq!(ptr, buf.bytes.as_ptr());
+/// assert_eq!(&buf.bytes[..len + 1], b"1234\0");
+/// ```
+///
+/// # Safety
+///
+/// `buf` must be null or valid for writes of an `FfiBuffer`, and `len` must
+/// be null or valid for writes of a `usize`.
+#[no_mangle]
+pub unsafe extern "C" fn itoa_format_u64(
+    buf: *mut FfiBuffer,
+    value: u64,
+    len: *mut usize,
+) -> *const u8 {
+    if buf.is_null() {
+        if !len.is_null() {
+            *len = 0;
+        }
+        return ptr::null();
+    }
+    let bytes = &mut (*buf).bytes;
+    let n = value.write_forward(bytes).unwrap();
+    bytes[n] = b'\0';
+    if !len.is_null() {
+        *len = n;
+    }
+    bytes.as_ptr()
+}
@@ -0,0 +1,16 @@
+use heapless::Vec;
+use {Buffer, Integer};
+
+/// Append integer to a `heapless::Vec<u8, N>`.
+///
+/// Returns `Err(())` without modifying the vector if the formatted integer
+/// does not fit in its remaining capacity.
+#[inline]
+#[allow(clippy::result_unit_err)] // same as heapless::Vec::extend_from_slice
+pub fn extend_heapless<const N: usize, V: Integer>(
+    vec: &mut Vec<u8, N>,
+    value: V,
+) -> Result<(), ()> {
+    let mut buf = Buffer::new();
+    vec.extend_from_slice(buf.format(value).as_bytes())
+}
@@ -55,28 +55,131 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! <br>
+//!
+//! # Panics
+//!
+//! No value of any integer type makes formatting panic, and widths and sizes
+//! saturate at the capacity of the output rather than overflowing, so
+//! `min_width = usize::MAX` is allowed. The only panics are for arguments that
+//! are invalid whatever the value, such as a radix outside `2..=36` or a fill
+//! byte that is not ASCII, and each function lists those under its own
+//! Panics heading.
 
 #![doc(html_root_url = "https://docs.rs/itoa/0.4.7")]
 #![cfg_attr(not(feature = "std"), no_std)]
-#![cfg_attr(feature = "cargo-clippy", allow(renamed_and_removed_lints))]
+// The C entry point of the ffi feature cannot be written without unsafe code,
+// and neither can `write_uninit` on compilers older than 1.93. `forbid` cannot
+// be lifted for a single item, so those builds are `deny` with an allow on just
+// the ffi module and `init_uninit`.
 #![cfg_attr(
-    feature = "cargo-clippy",
-    allow(
-        expl_impl_clone_on_copy,
-        missing_errors_doc,
-        must_use_candidate,
-        transmute_ptr_to_ptr
-    )
+    all(
+        feature = "safe",
+        not(feature = "ffi"),
+        not(no_maybe_uninit_write_slice)
+    ),
+    forbid(unsafe_code)
+)]
+#![cfg_attr(
+    all(feature = "safe", any(feature = "ffi", no_maybe_uninit_write_slice)),
+    deny(unsafe_code)
+)]
+#![allow(
+    clippy::expl_impl_clone_on_copy,
+    clippy::missing_errors_doc,
+    clippy::must_use_candidate
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "arrayvec")]
+extern crate arrayvec;
+
+#[cfg(feature = "bytes")]
+extern crate bytes;
+
+#[cfg(feature = "ethnum")]
+extern crate ethnum;
+
+#[cfg(feature = "heapless")]
+extern crate heapless;
+
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+
+// arraystring, heapless_vec and padded_array use const generics, which are
+// newer than the rest of the crate's minimum supported compiler, so they are
+// kept out of lib.rs.
+#[cfg(feature = "arrayvec")]
+mod arraystring;
+mod ebcdic;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+mod ffi;
+#[cfg(feature = "heapless")]
+mod heapless_vec;
+#[cfg(not(no_const_generics))]
+mod padded_array;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(all(
+    feature = "simd",
+    not(feature = "safe"),
+    any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        all(target_arch = "aarch64", target_feature = "neon", not(no_aarch64_neon)),
+    )
+))]
+#[allow(clippy::incompatible_msrv)] // NEON is gated on no_aarch64_neon
+mod simd;
+#[cfg(feature = "ethnum")]
+mod u256;
 #[cfg(feature = "i128")]
 mod udiv128;
 
+#[cfg(feature = "arrayvec")]
+pub use arraystring::push_arraystring;
+pub use ebcdic::format_ebcdic;
+#[cfg(feature = "ffi")]
+pub use ffi::{itoa_format_u64, FfiBuffer};
+#[cfg(feature = "heapless")]
+pub use heapless_vec::extend_heapless;
+#[cfg(not(no_const_generics))]
+pub use padded_array::to_padded_array;
+
 #[cfg(feature = "std")]
-use std::{fmt, io, mem, ptr, slice, str};
+use std::mem::MaybeUninit;
+#[cfg(all(feature = "std", not(feature = "safe")))]
+use std::ptr;
+#[cfg(all(
+    feature = "std",
+    any(not(feature = "safe"), no_maybe_uninit_write_slice)
+))]
+use std::slice;
+#[cfg(feature = "std")]
+use std::{cmp, convert, fmt, hash, io, mem, num, str};
 
 #[cfg(not(feature = "std"))]
-use core::{fmt, mem, ptr, slice, str};
+use core::mem::MaybeUninit;
+#[cfg(all(not(feature = "std"), not(feature = "safe")))]
+use core::ptr;
+#[cfg(all(
+    not(feature = "std"),
+    any(not(feature = "safe"), no_maybe_uninit_write_slice)
+))]
+use core::slice;
+#[cfg(not(feature = "std"))]
+use core::{cmp, convert, fmt, hash, mem, num, str};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Write integer to an `io::Write`.
 #[cfg(feature = "std")]
@@ -90,100 +193,3069 @@ pub fn write<W: io::Write, V: Integer>(mut wr: W, value: V) -> io::Result<usize>
     }
 }
 
-/// Write integer to an `fmt::Write`.
+/// Write integer to an `io::Write` as a single length byte followed by the
+/// digits.
+///
+/// The longest output of any integer type is 40 bytes, so the length always
+/// fits in the one byte.
+///
+/// ```
+/// let mut wire = Vec::new();
+/// itoa::write_len_prefixed(&mut wire, -42i32).unwrap();
+/// assert_eq!(wire, b"\x03-42");
+/// ```
+#[cfg(feature = "std")]
+pub fn write_len_prefixed<W: io::Write, V: Integer>(wr: &mut W, value: V) -> io::Result<()> {
+    let mut buf = Buffer::new();
+    let s = buf.format(value);
+    wr.write_all(&[s.len() as u8])?;
+    wr.write_all(s.as_bytes())
+}
+
+/// Write integer to an `io::Write` in the same frame as
+/// [`write_len_prefixed`], with the length byte and the digits passed together
+/// to `write_vectored`.
+///
+/// Writers that support vectored writes take the whole frame in one call. A
+/// partial write is continued with the remaining bytes until the frame is
+/// written, the same as `write_all` does.
+///
+/// ```
+/// let mut wire = Vec::new();
+/// itoa::write_vectored(&mut wire, -42i32).unwrap();
+/// assert_eq!(wire, b"\x03-42");
+/// ```
+#[cfg(feature = "std")]
+pub fn write_vectored<W: io::Write, V: Integer>(wr: &mut W, value: V) -> io::Result<()> {
+    let mut buf = Buffer::new();
+    let digits = buf.format(value).as_bytes();
+    let prefix = [digits.len() as u8];
+    let mut written = 0;
+    while written < 1 + digits.len() {
+        let result = if written == 0 {
+            wr.write_vectored(&[io::IoSlice::new(&prefix), io::IoSlice::new(digits)])
+        } else {
+            wr.write_vectored(&[io::IoSlice::new(&digits[written - 1..])])
+        };
+        match result {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            Ok(n) => written += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Write integer to an `io::Write` followed by a `\n`, in a single
+/// `write_all` call.
+///
+/// ```
+/// let mut lines = Vec::new();
+/// itoa::writeln(&mut lines, 1u8).unwrap();
+/// itoa::writeln(&mut lines, -20i32).unwrap();
+/// assert_eq!(lines, b"1\n-20\n");
+/// ```
+#[cfg(feature = "std")]
+pub fn writeln<W: io::Write, V: Integer>(wr: &mut W, value: V) -> io::Result<()> {
+    // The digits end right before the newline in the last byte.
+    let mut line = [BUFFER_BYTE_INIT; MAX_LEN + 1];
+    let len = value.write_end(&mut line[..MAX_LEN]).len();
+    set_byte(&mut line, MAX_LEN, b'\n');
+    wr.write_all(buffer_ascii(&line[MAX_LEN - len..]).as_bytes())
+}
+
+/// Append integer to a `Vec<u8>`.
+#[cfg(feature = "alloc")]
 #[inline]
-pub fn fmt<W: fmt::Write, V: Integer>(mut wr: W, value: V) -> fmt::Result {
+pub fn push_to_vec<V: Integer>(vec: &mut Vec<u8>, value: V) {
     let mut buf = Buffer::new();
-    wr.write_str(buf.format(value))
+    vec.extend_from_slice(buf.format(value).as_bytes());
 }
 
-/// A safe API for formatting integers to text.
+/// Append integer to a `String`.
 ///
-/// # Example
+/// The digits are written straight into the spare capacity of the `String`,
+/// after reserving room for the longest value of the integer type.
 ///
 /// ```
-/// let mut buffer = itoa::Buffer::new();
-/// let printed = buffer.format(1234);
-/// assert_eq!(printed, "1234");
+/// let mut json = String::from("{\"id\":");
+/// itoa::push_to_string(&mut json, 42u64);
+/// json.push('}');
+/// assert_eq!(json, "{\"id\":42}");
 /// ```
-#[derive(Copy)]
-pub struct Buffer {
-    bytes: [u8; I128_MAX_LEN],
+#[cfg(all(feature = "alloc", not(feature = "safe")))]
+#[inline]
+pub fn push_to_string<V: Integer>(s: &mut String, value: V) {
+    let start = s.len();
+    s.reserve(V::MAX_STR_LEN);
+    // The digits are ASCII, so the string stays UTF-8.
+    unsafe {
+        let vec = s.as_mut_vec();
+        let dst = vec.as_mut_ptr().add(start);
+        let spare = slice::from_raw_parts_mut(dst as *mut MaybeUninit<u8>, V::MAX_STR_LEN);
+        let bytes = value.write_uninit(spare);
+        let len = bytes.len();
+        // write_uninit right-aligns the digits, so move them to the front.
+        ptr::copy(bytes.as_ptr(), dst, len);
+        vec.set_len(start + len);
+    }
 }
 
-impl Default for Buffer {
+/// Append integer to a `String`.
+///
+/// ```
+/// let mut json = String::from("{\"id\":");
+/// itoa::push_to_string(&mut json, 42u64);
+/// json.push('}');
+/// assert_eq!(json, "{\"id\":42}");
+/// ```
+#[cfg(all(feature = "alloc", feature = "safe"))]
+#[inline]
+pub fn push_to_string<V: Integer>(s: &mut String, value: V) {
+    let mut buf = Buffer::new();
+    s.push_str(buf.format(value));
+}
+
+/// Concatenate a prefix and an integer into a new `String`.
+///
+/// The `String` is allocated once with room for the prefix and the longest
+/// value of the integer type, and the digits are written into its spare
+/// capacity by [`push_to_string`].
+///
+/// ```
+/// assert_eq!(itoa::concat("user:", 12345u32), "user:12345");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn concat<V: Integer>(prefix: &str, value: V) -> String {
+    let mut s = String::with_capacity(prefix.len() + V::MAX_STR_LEN);
+    s.push_str(prefix);
+    push_to_string(&mut s, value);
+    s
+}
+
+/// Format integer into a `Cow`, borrowing a static string for small values.
+///
+/// Values from 0 through 255 are returned as `Cow::Borrowed` from a single
+/// static table without allocating. Anything else is formatted into a new
+/// `String`.
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// assert_eq!(itoa::format_cow(42u32), Cow::Borrowed("42"));
+/// assert_eq!(itoa::format_cow(-42i32), Cow::<str>::Owned(String::from("-42")));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn format_cow<V: Integer>(value: V) -> Cow<'static, str> {
+    let mut buf = Buffer::new();
+    let s = buf.format(value);
+    let bytes = s.as_bytes();
+    if bytes[0] != b'-' && (bytes.len() < 3 || bytes.len() == 3 && s <= "255") {
+        let n = bytes.iter().fold(0, |n, &b| n * 10 + (b - b'0') as usize);
+        let start = match bytes.len() {
+            1 => n,
+            2 => 10 + (n - 10) * 2,
+            _ => 190 + (n - 100) * 3,
+        };
+        return Cow::Borrowed(&SMALL_INTS[start..start + bytes.len()]);
+    }
+    Cow::Owned(String::from(s))
+}
+
+// Every integer from 0 through 255, concatenated.
+#[cfg(feature = "alloc")]
+const SMALL_INTS: &str = "\
+    012345678910111213141516171819202122232425262728293031323334353637383940\
+    414243444546474849505152535455565758596061626364656667686970717273747576\
+    777879808182838485868788899091929394959697989910010110210310410510610710\
+    810911011111211311411511611711811912012112212312412512612712812913013113\
+    213313413513613713813914014114214314414514614714814915015115215315415515\
+    615715815916016116216316416516616716816917017117217317417517617717817918\
+    018118218318418518618718818919019119219319419519619719819920020120220320\
+    420520620720820921021121221321421521621721821922022122222322422522622722\
+    822923023123223323423523623723823924024124224324424524624724824925025125\
+    2253254255";
+
+/// Format a slice of integers into one contiguous byte buffer.
+///
+/// Returns the concatenated digits together with the offset at which each value
+/// starts. There is one more offset than there are values, so value `i` is
+/// `bytes[offsets[i]..offsets[i + 1]]`.
+///
+/// ```
+/// let (bytes, offsets) = itoa::format_all(&[7, 42, 1000]);
+/// assert_eq!(bytes, b"7421000");
+/// assert_eq!(offsets, [0, 1, 3, 7]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the total length does not fit in a `u32`.
+#[cfg(feature = "alloc")]
+pub fn format_all(values: &[u64]) -> (Vec<u8>, Vec<u32>) {
+    let mut buf = Buffer::new();
+    let mut bytes = Vec::new();
+    let mut offsets = Vec::with_capacity(values.len() + 1);
+    offsets.push(0);
+    for &value in values {
+        bytes.extend_from_slice(buf.format(value).as_bytes());
+        assert!(
+            bytes.len() <= <u32>::max_value() as usize,
+            "formatted length overflows u32"
+        );
+        offsets.push(bytes.len() as u32);
+    }
+    (bytes, offsets)
+}
+
+/// Format a slice of integers into one `String` each.
+///
+/// A single [`Buffer`] is reused for the whole column, and each `String` is
+/// allocated once at exactly the length of its value.
+///
+/// ```
+/// let column = itoa::format_column(&[7, -42]);
+/// assert_eq!(column, ["7", "-42"]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn format_column(values: &[i64]) -> Vec<String> {
+    let mut buf = Buffer::new();
+    let mut column = Vec::with_capacity(values.len());
+    for &value in values {
+        column.push(String::from(buf.format(value)));
+    }
+    column
+}
+
+/// Append a slice of integers to a `Vec<u8>`, with `separator` between
+/// consecutive values.
+///
+/// The vector is reserved once up front with room for the longest value of
+/// `I` at every position. Nothing is appended for an empty slice, and the
+/// existing content of `out` is kept.
+///
+/// ```
+/// let mut out = b"[".to_vec();
+/// itoa::join_to_vec(&[1, -20, 300], b',', &mut out);
+/// out.push(b']');
+/// assert_eq!(out, b"[1,-20,300]");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn join_to_vec<I: Integer + Copy>(values: &[I], separator: u8, out: &mut Vec<u8>) {
+    let mut iter = values.iter();
+    if let Some(&first) = iter.next() {
+        out.reserve(values.len() * I::MAX_STR_LEN + values.len() - 1);
+        let mut buf = Buffer::new();
+        out.extend_from_slice(buf.format(first).as_bytes());
+        for &value in iter {
+            out.push(separator);
+            out.extend_from_slice(buf.format(value).as_bytes());
+        }
+    }
+}
+
+/// Write integer to a `bytes::BufMut`.
+#[cfg(feature = "bytes")]
+#[inline]
+pub fn put<B: bytes::BufMut, V: Integer>(buf: &mut B, value: V) {
+    let mut buffer = Buffer::new();
+    buf.put_slice(buffer.format(value).as_bytes());
+}
+
+/// Append integer to a `smallvec::SmallVec<A>`.
+#[cfg(feature = "smallvec")]
+#[inline]
+pub fn extend_smallvec<A: smallvec::Array<Item = u8>, V: Integer>(
+    sv: &mut smallvec::SmallVec<A>,
+    value: V,
+) {
+    let mut buf = Buffer::new();
+    sv.extend_from_slice(buf.format(value).as_bytes());
+}
+
+/// Write integer to an `fmt::Write` with `separator` inserted between every
+/// `group_size` digits counting from the right.
+///
+/// Unlike [`WideBuffer::format_grouped`](struct.WideBuffer.html#method.format_grouped),
+/// the separator may be any string, such as a thin space.
+///
+/// ```
+/// let mut s = String::new();
+/// itoa::fmt_grouped(&mut s, -1234567, "\u{2009}", 3).unwrap();
+/// assert_eq!(s, "-1\u{2009}234\u{2009}567");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `group_size` is zero.
+pub fn fmt_grouped<W: fmt::Write, V: Integer>(
+    mut wr: W,
+    value: V,
+    separator: &str,
+    group_size: usize,
+) -> fmt::Result {
+    assert!(group_size != 0, "group_size must be nonzero");
+
+    let mut buf = Buffer::new();
+    let s = buf.format(value);
+    write_grouped(&mut wr, s, separator, group_size)
+}
+
+/// Write an unsigned 128-bit integer to an `fmt::Write` in the given radix,
+/// with `separator` inserted between every `group_size` digits counting from
+/// the right.
+///
+/// Digits above 9 are the lowercase letters `a` through `z`, the same as
+/// [`WideBuffer::format_radix`](struct.WideBuffer.html#method.format_radix).
+///
+/// ```
+/// let mut s = String::new();
+/// itoa::format_radix_grouped(&mut s, 0xDEADBEEF, 16, 4, '_').unwrap();
+/// assert_eq!(s, "dead_beef");
+/// ```
+///
+/// # Panics
+///
+/// Panics if `radix` is not in the range `2..=36` or `group_size` is zero.
+#[cfg(feature = "i128")]
+pub fn format_radix_grouped<W: fmt::Write>(
+    wr: &mut W,
+    value: u128,
+    radix: u32,
+    group_size: usize,
+    separator: char,
+) -> fmt::Result {
+    assert!(group_size != 0, "group_size must be nonzero");
+
+    let mut buf = WideBuffer::new();
+    let s = buf.format_radix(value, radix);
+    let mut utf8 = [0u8; 4];
+    write_grouped(wr, s, separator.encode_utf8(&mut utf8), group_size)
+}
+
+// Write the digits of `s`, after any `-`, with `separator` between groups.
+fn write_grouped<W: fmt::Write>(
+    wr: &mut W,
+    mut s: &str,
+    separator: &str,
+    group_size: usize,
+) -> fmt::Result {
+    if s.as_bytes()[0] == b'-' {
+        wr.write_str("-")?;
+        s = &s[1..];
+    }
+    let mut first = s.len() % group_size;
+    if first == 0 {
+        first = group_size;
+    }
+    wr.write_str(&s[..first])?;
+    let mut rest = &s[first..];
+    while !rest.is_empty() {
+        wr.write_str(separator)?;
+        wr.write_str(&rest[..group_size])?;
+        rest = &rest[group_size..];
+    }
+    Ok(())
+}
+
+/// Write integer to an `fmt::Write` as a Rust integer literal, with `_`
+/// between every 3 digits counting from the right.
+///
+/// The sign of a negative value stays in front, so the output can be pasted
+/// into generated Rust source as is.
+///
+/// ```
+/// let mut s = String::new();
+/// itoa::format_rust_literal(&mut s, -1000000).unwrap();
+/// assert_eq!(s, "-1_000_000");
+/// ```
+#[inline]
+pub fn format_rust_literal<W: fmt::Write, V: Integer>(wr: &mut W, value: V) -> fmt::Result {
+    fmt_grouped(wr, value, "_", 3)
+}
+
+/// Integer type wider than the primitives, which can be formatted by
+/// [`itoa::fmt_chunked`](fn.fmt_chunked.html).
+///
+/// Implementing this for a big integer type, such as a 256-bit integer from
+/// another crate, only requires division by 10<sup>19</sup>, which is the
+/// highest power of 10 less than 2<sup>64</sup>. With the `ethnum` feature it
+/// is implemented for `ethnum::U256` and `ethnum::I256`.
+pub trait DivMod1e19: Copy {
+    /// Divide by 10<sup>19</sup> and return the quotient and remainder.
+    ///
+    /// For a signed type the quotient is rounded toward zero and the
+    /// remainder is returned as its magnitude.
+    fn divmod_1e19(self) -> (Self, u64);
+
+    /// Whether this value is zero.
+    fn is_zero(&self) -> bool;
+
+    /// Whether this value is less than zero, which is never the case for an
+    /// unsigned type.
     #[inline]
-    fn default() -> Buffer {
-        Buffer::new()
+    fn is_negative(&self) -> bool {
+        false
     }
 }
 
-impl Clone for Buffer {
+impl DivMod1e19 for u64 {
     #[inline]
-    fn clone(&self) -> Self {
-        Buffer::new()
+    fn divmod_1e19(self) -> (Self, u64) {
+        let d = 10_000_000_000_000_000_000_u64;
+        (self / d, self % d)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == 0
     }
 }
 
-impl Buffer {
-    /// This is a cheap operation; you don't need to worry about reusing buffers
-    /// for efficiency.
+#[cfg(feature = "i128")]
+impl DivMod1e19 for u128 {
     #[inline]
-    #[allow(deprecated)]
-    pub fn new() -> Buffer {
-        Buffer {
-            bytes: unsafe { mem::uninitialized() },
+    fn divmod_1e19(self) -> (Self, u64) {
+        udiv128::udivmod_1e19(self)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+}
+
+/// Write an integer of any width to an `fmt::Write`, 19 digits at a time.
+///
+/// This is the approach used for 128-bit integers, generalized to any type
+/// implementing [`DivMod1e19`](trait.DivMod1e19.html). The value is divided
+/// by 10<sup>19</sup> repeatedly and each remainder is formatted as a `u64`,
+/// so the only wide arithmetic is the division.
+///
+/// ```
+/// let mut s = String::new();
+/// itoa::fmt_chunked(&mut s, 12345u64).unwrap();
+/// assert_eq!(s, "12345");
+/// ```
+pub fn fmt_chunked<W: fmt::Write, N: DivMod1e19>(mut wr: W, value: N) -> fmt::Result {
+    if value.is_negative() {
+        wr.write_char('-')?;
+    }
+    write_chunks(&mut wr, value)
+}
+
+// Writes the most significant chunks first by recursing on the quotient.
+fn write_chunks<W: fmt::Write, N: DivMod1e19>(wr: &mut W, value: N) -> fmt::Result {
+    let (high, low) = value.divmod_1e19();
+    if !high.is_zero() {
+        write_chunks(wr, high)?;
+        let mut buf = Buffer::new();
+        return wr.write_str(buf.format_padded(low, 19));
+    }
+    let mut buf = Buffer::new();
+    wr.write_str(buf.format(low))
+}
+
+/// Write integer to an `fmt::Write` using caller-provided glyphs for the
+/// decimal digits 0 through 9 and for the sign.
+///
+/// Each glyph may be any string, so numeral systems whose digits are not
+/// ASCII, such as Devanagari or Arabic-Indic, are supported.
+///
+/// ```
+/// const DEVANAGARI: [&str; 10] = ["०", "१", "२", "३", "४", "५", "६", "७", "८", "९"];
+///
+/// let mut s = String::new();
+/// itoa::format_with_digits(&mut s, -2024, &DEVANAGARI, "-").unwrap();
+/// assert_eq!(s, "-२०२४");
+/// ```
+pub fn format_with_digits<W: fmt::Write, I: Integer>(
+    wr: &mut W,
+    value: I,
+    digits: &[&str; 10],
+    minus: &str,
+) -> fmt::Result {
+    let mut buf = Buffer::new();
+    let mut s = buf.format(value);
+    if s.as_bytes()[0] == b'-' {
+        wr.write_str(minus)?;
+        s = &s[1..];
+    }
+    for &b in s.as_bytes() {
+        wr.write_str(digits[(b - b'0') as usize])?;
+    }
+    Ok(())
+}
+
+const SUPERSCRIPT_DIGITS: [&str; 10] = [
+    "\u{2070}", "\u{b9}", "\u{b2}", "\u{b3}", "\u{2074}", "\u{2075}", "\u{2076}", "\u{2077}",
+    "\u{2078}", "\u{2079}",
+];
+
+const SUBSCRIPT_DIGITS: [&str; 10] = [
+    "\u{2080}", "\u{2081}", "\u{2082}", "\u{2083}", "\u{2084}", "\u{2085}", "\u{2086}", "\u{2087}",
+    "\u{2088}", "\u{2089}",
+];
+
+/// Write integer to an `fmt::Write` in Unicode superscript digits, with
+/// `U+207B SUPERSCRIPT MINUS` for the sign.
+///
+/// ```
+/// let mut s = String::new();
+/// itoa::format_superscript(&mut s, -12).unwrap();
+/// assert_eq!(s, "⁻¹²");
+/// ```
+#[inline]
+pub fn format_superscript<W: fmt::Write>(wr: &mut W, value: i32) -> fmt::Result {
+    format_with_digits(wr, value, &SUPERSCRIPT_DIGITS, "\u{207b}")
+}
+
+/// Write integer to an `fmt::Write` in Unicode subscript digits, with
+/// `U+208B SUBSCRIPT MINUS` for the sign.
+///
+/// ```
+/// let mut s = String::new();
+/// itoa::format_subscript(&mut s, 2).unwrap();
+/// assert_eq!(s, "₂");
+/// ```
+#[inline]
+pub fn format_subscript<W: fmt::Write>(wr: &mut W, value: i32) -> fmt::Result {
+    format_with_digits(wr, value, &SUBSCRIPT_DIGITS, "\u{208b}")
+}
+
+/// Write a slice of integers to an `fmt::Write`, with `separator` between
+/// consecutive values.
+///
+/// Nothing is written for an empty slice.
+///
+/// ```
+/// let mut row = String::new();
+/// itoa::join_into(&mut row, &[1, -20, 300], ",").unwrap();
+/// assert_eq!(row, "1,-20,300");
+/// ```
+pub fn join_into<W: fmt::Write, I: Integer + Copy>(
+    wr: &mut W,
+    values: &[I],
+    separator: &str,
+) -> fmt::Result {
+    let mut buf = Buffer::new();
+    let mut iter = values.iter();
+    if let Some(&first) = iter.next() {
+        wr.write_str(buf.format(first))?;
+        for &value in iter {
+            wr.write_str(separator)?;
+            wr.write_str(buf.format(value))?;
         }
     }
+    Ok(())
+}
 
-    /// Print an integer into this buffer and return a reference to its string representation
-    /// within the buffer.
-    pub fn format<I: Integer>(&mut self, i: I) -> &str {
-        i.write(self)
+/// Number of bytes that formatting an integer in base 10 produces, including
+/// the `-` of a negative value.
+///
+/// ```
+/// assert_eq!(itoa::written_len(-1234i32), 5);
+/// ```
+#[inline]
+pub fn written_len<V: Integer>(value: V) -> usize {
+    value.written_len()
+}
+
+/// Absolute value of an integer as the unsigned type of the same width.
+///
+/// This does not overflow for the minimum value of a signed type, and is the
+/// identity on unsigned types.
+///
+/// ```
+/// assert_eq!(itoa::unsigned_abs(<i8>::min_value()), 128u8);
+/// assert_eq!(itoa::unsigned_abs(-42i64), 42u64);
+/// ```
+#[inline]
+pub fn unsigned_abs<I: Integer>(i: I) -> I::Unsigned {
+    i.unsigned_abs()
+}
+
+/// Largest number of bytes that formatting any value of `I` in base 10
+/// produces, including the `-` of a negative value.
+///
+/// Unlike [`written_len`](fn.written_len.html) this is usable in a const
+/// context, such as the length of an array.
+///
+/// ```
+/// const ROW_LEN: usize = 4 * itoa::max_len::<i32>();
+///
+/// let row = [0u8; ROW_LEN];
+/// assert_eq!(row.len(), 44);
+/// ```
+#[cfg(not(no_const_fn_trait_bound))]
+#[inline]
+pub const fn max_len<I: Integer>() -> usize {
+    I::MAX_STR_LEN
+}
+
+/// Format a `u64` in base 10 in a const context.
+///
+/// Returns an array whose first `len` bytes are the digits, along with `len`.
+/// This is slower than [`Buffer::format`](struct.Buffer.html#method.format)
+/// and is meant for building tables at compile time.
+///
+/// ```
+/// const TEN_THOUSAND: ([u8; 20], usize) = itoa::const_format_u64(10_000);
+///
+/// let (bytes, len) = TEN_THOUSAND;
+/// assert_eq!(&bytes[..len], b"10000");
+/// ```
+#[cfg(not(no_const_loop))]
+pub const fn const_format_u64(n: u64) -> ([u8; 20], usize) {
+    let mut len = 1;
+    let mut rest = n / 10;
+    while rest != 0 {
+        len += 1;
+        rest /= 10;
+    }
+
+    let mut bytes = [0u8; 20];
+    let mut n = n;
+    let mut i = len;
+    while i != 0 {
+        i -= 1;
+        bytes[i] = b'0' + (n % 10) as u8;
+        n /= 10;
     }
+    (bytes, len)
 }
 
-// Seal to prevent downstream implementations of the Integer trait.
-mod private {
-    pub trait Sealed {}
+/// Write integer to an `fmt::Write`.
+#[inline]
+pub fn fmt<W: fmt::Write, V: Integer>(mut wr: W, value: V) -> fmt::Result {
+    let mut buf = Buffer::new();
+    wr.write_str(buf.format(value))
 }
 
-/// An integer that can be formatted by `itoa::write` and `itoa::fmt`.
-///
-/// This trait is sealed and cannot be implemented for types outside of itoa.
-pub trait Integer: private::Sealed {
+/// Write at most `max_bytes` bytes of an integer to an `fmt::Write`, and
+/// return how many were written.
+///
+/// When the integer is longer than `max_bytes`, its first `max_bytes` bytes
+/// are written, so the most significant digits (and the sign) are kept and the
+/// rest are dropped. Unlike [`format_into_truncating`] no marker is added, so
+/// the caller can compare the return value against [`Integer::MAX_STR_LEN`]
+/// or the full length to tell that it was cut short. A `max_bytes` of 0 writes
+/// nothing.
+///
+/// ```
+/// let mut s = String::new();
+/// assert_eq!(itoa::fmt_bounded(&mut s, -12345, 16), Ok(6));
+/// assert_eq!(itoa::fmt_bounded(&mut s, 98765, 3), Ok(3));
+/// assert_eq!(s, "-12345987");
+/// ```
+pub fn fmt_bounded<W: fmt::Write, I: Integer>(
+    w: &mut W,
+    value: I,
+    max_bytes: usize,
+) -> Result<usize, fmt::Error> {
+    let mut buf = Buffer::new();
+    let s = buf.format(value);
+    // Every byte is ASCII, so any prefix is a valid str.
+    let s = &s[..cmp::min(s.len(), max_bytes)];
+    w.write_str(s)?;
+    Ok(s.len())
+}
+
+/// Write integers to an `fmt::Write` as one fixed-width record, such as the
+/// `%04d%08d` of a flat file.
+///
+/// Each field is a value and its width. The value is left-padded with `'0'`
+/// to that width the same as [`Buffer::format_padded`], with the sign of a
+/// negative value counting toward the width. The fields are written one after
+/// another with no separator.
+///
+/// Values are never truncated. If any value is wider than its field, `Err` is
+/// returned before anything is written, so a bad field cannot leave a partial
+/// record behind.
+///
+/// ```
+/// let mut s = String::new();
+/// itoa::format_fields(&mut s, &[(7, 4), (-42, 6), (123, 3)]).unwrap();
+/// assert_eq!(s, "0007-00042123");
+///
+/// assert!(itoa::format_fields(&mut s, &[(12345, 4)]).is_err());
+/// ```
+pub fn format_fields<W: fmt::Write>(w: &mut W, fields: &[(i64, usize)]) -> fmt::Result {
+    const ZEROS: &str = "0000000000000000";
+
+    for &(value, width) in fields {
+        if value.written_len() > width {
+            return Err(fmt::Error);
+        }
+    }
+    let mut buf = Buffer::new();
+    for &(value, width) in fields {
+        if value < 0 {
+            w.write_char('-')?;
+        }
+        let mut pad = width - value.written_len();
+        while pad > 0 {
+            let n = cmp::min(pad, ZEROS.len());
+            w.write_str(&ZEROS[..n])?;
+            pad -= n;
+        }
+        w.write_str(buf.format_abs(value))?;
+    }
+    Ok(())
+}
+
+/// Longest base 10 representation of any integer type, which is the 40 bytes
+/// of `i128::MIN`.
+///
+/// This is the largest [`Integer::MAX_STR_LEN`] of any type, regardless of
+/// whether the `i128` feature is enabled.
+pub const MAX_LEN: usize = 40;
+
+/// Uninitialized scratch space that fits any integer in base 10, for use with
+/// [`write_uninit`] and [`format_uninit`].
+///
+/// ```
+/// use std::mem::MaybeUninit;
+///
+/// let mut raw: itoa::RawBuffer = [MaybeUninit::uninit(); itoa::MAX_LEN];
+/// assert_eq!(itoa::write_uninit(&mut raw, <i64>::min_value()), b"-9223372036854775808");
+/// assert_eq!(itoa::format_uninit(&mut raw, 42u8), Some("42"));
+/// ```
+pub type RawBuffer = [MaybeUninit<u8>; MAX_LEN];
+
+/// Convert an integer to the type `Dst` and format it, or return `None` if it
+/// is out of range for `Dst`.
+///
+/// ```
+/// let mut buffer = itoa::Buffer::new();
+/// assert_eq!(itoa::format_as::<u32, i64>(&mut buffer, 42), Some("42"));
+/// assert_eq!(itoa::format_as::<u32, i64>(&mut buffer, -1), None);
+/// ```
+#[inline]
+pub fn format_as<Dst, Src>(buf: &mut Buffer, value: Src) -> Option<&str>
+where
+    Dst: Integer + convert::TryFrom<Src>,
+{
+    match Dst::try_from(value) {
+        Ok(narrowed) => Some(buf.format(narrowed)),
+        Err(_) => None,
+    }
+}
+
+/// Format a `u64` as the narrowest unsigned type that can hold it, and return
+/// the string together with the width of that type in bytes: 1, 2, 4 or 8.
+///
+/// The digits are the same whichever type is chosen.
+///
+/// ```
+/// let mut buffer = itoa::Buffer::new();
+/// assert_eq!(itoa::format_narrowest(&mut buffer, 255), ("255", 1));
+/// assert_eq!(itoa::format_narrowest(&mut buffer, 256), ("256", 2));
+/// ```
+pub fn format_narrowest(buf: &mut Buffer, value: u64) -> (&str, u8) {
+    if value <= <u8>::max_value() as u64 {
+        (buf.format(value as u8), 1)
+    } else if value <= <u16>::max_value() as u64 {
+        (buf.format(value as u16), 2)
+    } else if value <= <u32>::max_value() as u64 {
+        (buf.format(value as u32), 4)
+    } else {
+        (buf.format(value), 8)
+    }
+}
+
+/// Write integer to the end of a caller-owned uninitialized buffer.
+///
+/// This bypasses [`Buffer`] for callers that manage their own memory. The
+/// output is right-aligned so that it ends at the end of `buf`, and the
+/// returned slice covers exactly the bytes that were written. Nothing in front
+/// of them is touched.
+///
+/// # Panics
+///
+/// Panics if `buf` is shorter than [`Integer::MAX_STR_LEN`] for `V`, even if
+/// this particular value would fit.
+///
+/// ```
+/// use itoa::Integer;
+/// use std::mem::MaybeUninit;
+///
+/// let mut buf = [MaybeUninit::<u8>::uninit(); i32::MAX_STR_LEN];
+/// assert_eq!(itoa::write_uninit(&mut buf, -42i32), b"-42");
+/// ```
+#[inline]
+pub fn write_uninit<V: Integer>(buf: &mut [MaybeUninit<u8>], value: V) -> &[u8] {
+    value.write_uninit(buf)
+}
+
+/// Write integer to the end of a caller-owned uninitialized buffer, or return
+/// `None` if the buffer is too short.
+///
+/// This is the same as [`write_uninit`] except that a `dst` shorter than
+/// [`Integer::MAX_STR_LEN`] for `V` gives `None` instead of a panic, and the
+/// output is returned as a `&str`.
+///
+/// ```
+/// use std::mem::MaybeUninit;
+///
+/// let mut arena = [MaybeUninit::<u8>::uninit(); 8];
+/// assert_eq!(itoa::format_uninit(&mut arena, -42i16), Some("-42"));
+/// assert_eq!(itoa::format_uninit(&mut arena, 42u64), None);
+/// ```
+#[inline]
+pub fn format_uninit<V: Integer>(dst: &mut [MaybeUninit<u8>], value: V) -> Option<&str> {
+    if dst.len() < V::MAX_STR_LEN {
+        return None;
+    }
+    let bytes = value.write_uninit(dst);
+    Some(ascii(bytes))
+}
+
+/// Write integer to the start of a byte slice.
+///
+/// Returns the written prefix of `dst` as a string, or `Err(())` without
+/// modifying `dst` if it is too short to hold the integer.
+///
+/// ```
+/// let mut arena = [0u8; 64];
+/// let printed = itoa::format_into(&mut arena, -128i8).unwrap();
+/// assert_eq!(printed, "-128");
+/// ```
+#[inline]
+#[allow(clippy::result_unit_err)]
+pub fn format_into<V: Integer>(dst: &mut [u8], value: V) -> Result<&str, ()> {
+    let mut buf = Buffer::new();
+    let s = buf.format(value);
+    if s.len() > dst.len() {
+        return Err(());
+    }
+    let dst = &mut dst[..s.len()];
+    dst.copy_from_slice(s.as_bytes());
+    Ok(ascii(dst))
+}
+
+/// Write integer to the start of a byte slice, truncating it if the slice is
+/// too short, and return the written prefix of `dst` as a string.
+///
+/// When the integer does not fit, as many of its most significant digits as
+/// possible are written, followed by a `*` in the last byte of `dst` to mark
+/// the truncation. An ASCII marker is used so that it always takes a single
+/// byte. An empty `dst` gives an empty string. This never panics.
+///
+/// ```
+/// let mut dst = [0u8; 4];
+/// assert_eq!(itoa::format_into_truncating(&mut dst, 123), "123");
+/// assert_eq!(itoa::format_into_truncating(&mut dst, 123456), "123*");
+/// ```
+pub fn format_into_truncating(dst: &mut [u8], value: u64) -> &str {
+    let mut buf = Buffer::new();
+    let s = buf.format(value).as_bytes();
+    let len = if s.len() <= dst.len() {
+        dst[..s.len()].copy_from_slice(s);
+        s.len()
+    } else if let Some((marker, digits)) = dst.split_last_mut() {
+        digits.copy_from_slice(&s[..digits.len()]);
+        *marker = b'*';
+        dst.len()
+    } else {
+        0
+    };
+    ascii(&dst[..len])
+}
+
+/// Write integer to the start of a byte slice without an intermediate buffer.
+///
+/// The number of bytes is computed up front, so the digits are written
+/// directly into `dst[..len]` rather than being formatted at the end of a
+/// [`Buffer`] and copied. Returns the number of bytes written, or `None`
+/// without modifying `dst` if it is too short. The output is the same as that
+/// of [`format_into`].
+///
+/// ```
+/// let mut dst = [0u8; 16];
+/// assert_eq!(itoa::write_forward(&mut dst, -1234i32), Some(5));
+/// assert_eq!(&dst[..5], b"-1234");
+/// ```
+#[inline]
+pub fn write_forward<V: Integer>(dst: &mut [u8], value: V) -> Option<usize> {
+    value.write_forward(dst)
+}
+
+/// Write integer across two byte slices, such as the two free regions of a
+/// ring buffer that has wrapped around.
+///
+/// The bytes fill `first` from its start and continue at the start of
+/// `second`. Returns the total number of bytes written, or `None` without
+/// modifying either slice if their combined length is too short.
+///
+/// ```
+/// let mut first = [0u8; 3];
+/// let mut second = [0u8; 8];
+/// assert_eq!(itoa::format_split(&mut first, &mut second, 123456), Some(6));
+/// assert_eq!(&first, b"123");
+/// assert_eq!(&second[..3], b"456");
+/// ```
+pub fn format_split(first: &mut [u8], second: &mut [u8], value: u64) -> Option<usize> {
+    let mut buf = Buffer::new();
+    let bytes = buf.format(value).as_bytes();
+    if bytes.len() > first.len() + second.len() {
+        return None;
+    }
+    let split = cmp::min(bytes.len(), first.len());
+    let (head, tail) = bytes.split_at(split);
+    first[..split].copy_from_slice(head);
+    second[..tail.len()].copy_from_slice(tail);
+    Some(bytes.len())
+}
+
+/// Write integer as packed BCD, two decimal digits per byte with the high
+/// nibble first.
+///
+/// The most significant digits go in `dst[0]`. An odd number of digits leaves
+/// the high nibble of the first byte zero. Returns the number of bytes
+/// written, or `None` without modifying `dst` if it is too short.
+///
+/// ```
+/// let mut dst = [0u8; 3];
+/// assert_eq!(itoa::to_bcd(12345, &mut dst), Some(3));
+/// assert_eq!(dst, [0x01, 0x23, 0x45]);
+/// ```
+pub fn to_bcd(value: u64, dst: &mut [u8]) -> Option<usize> {
+    let mut buf = Buffer::new();
+    let digits = buf.format(value).as_bytes();
+    let len = (digits.len() + 1) / 2;
+    if len > dst.len() {
+        return None;
+    }
+    let (head, pairs) = digits.split_at(digits.len() % 2);
+    let mut out = dst.iter_mut();
+    if let Some(&d) = head.first() {
+        *out.next().unwrap() = d - b'0';
+    }
+    for (pair, byte) in pairs.chunks(2).zip(out) {
+        *byte = (pair[0] - b'0') << 4 | (pair[1] - b'0');
+    }
+    Some(len)
+}
+
+/// Sum of the decimal digits of an integer, ignoring the sign.
+///
+/// ```
+/// assert_eq!(itoa::digit_sum(12345), 15);
+/// assert_eq!(itoa::digit_sum(-12345), 15);
+/// ```
+pub fn digit_sum<V: Integer>(value: V) -> u32 {
+    let mut buf = Buffer::new();
+    buf.format_abs(value)
+        .bytes()
+        .map(|b| (b - b'0') as u32)
+        .sum()
+}
+
+/// Luhn check digit of an integer, as a value from 0 through 9.
+///
+/// Appending the check digit to the decimal digits of `value` gives a number
+/// that passes the Luhn checksum used by credit card numbers.
+///
+/// ```
+/// assert_eq!(itoa::luhn_check_digit(7992739871), 3);
+/// ```
+pub fn luhn_check_digit(value: u64) -> u8 {
+    let mut buf = Buffer::new();
+    let mut sum = 0u32;
+    // The rightmost digit is doubled, because it ends up second from the
+    // right once the check digit is appended.
+    for (i, b) in buf.format(value).bytes().rev().enumerate() {
+        let mut d = (b - b'0') as u32;
+        if i % 2 == 0 {
+            d *= 2;
+            if d > 9 {
+                d -= 9;
+            }
+        }
+        sum += d;
+    }
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Iterate over the bytes of an integer's base 10 representation.
+///
+/// The integer is formatted up front into a buffer owned by the iterator, so
+/// the bytes come out left to right with the sign first.
+///
+/// ```
+/// let bytes: Vec<u8> = itoa::digits(-42i32).collect();
+/// assert_eq!(bytes, b"-42");
+/// ```
+#[inline]
+pub fn digits<V: Integer>(value: V) -> Digits {
+    let mut buf = Buffer::new();
+    let len = buf.format(value).len();
+    Digits {
+        buf,
+        pos: BUFFER_LEN - len,
+    }
+}
+
+/// Iterate over the base 10 digits of an integer's magnitude, without the sign.
+///
+/// ```
+/// let bytes: Vec<u8> = itoa::unsigned_digits(-42i32).collect();
+/// assert_eq!(bytes, b"42");
+/// ```
+#[inline]
+pub fn unsigned_digits<V: Integer>(value: V) -> Digits {
+    let mut buf = Buffer::new();
+    let len = buf.format_abs(value).len();
+    Digits {
+        buf,
+        pos: BUFFER_LEN - len,
+    }
+}
+
+/// Iterator returned by [`itoa::digits`](fn.digits.html) and
+/// [`itoa::unsigned_digits`](fn.unsigned_digits.html).
+pub struct Digits {
+    buf: Buffer,
+    pos: usize,
+}
+
+impl Iterator for Digits {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.pos == BUFFER_LEN {
+            return None;
+        }
+        let byte = get_byte(&self.buf.bytes, self.pos);
+        self.pos += 1;
+        Some(byte)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = BUFFER_LEN - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Digits {}
+
+/// Format an integer in base 10 into an array returned by value.
+///
+/// ```
+/// let formatted = itoa::to_array(-42i32);
+/// assert_eq!(formatted.as_str(), "-42");
+/// ```
+#[inline]
+pub fn to_array<V: Integer>(value: V) -> Formatted {
+    let mut buf = Buffer::new();
+    let s = buf.format(value);
+    let mut bytes = [0u8; FORMATTED_LEN];
+    bytes[FORMATTED_START..FORMATTED_START + s.len()].copy_from_slice(s.as_bytes());
+    Formatted {
+        bytes,
+        len: s.len(),
+    }
+}
+
+// Widest padded output of `Formatted::pad_left` and `Formatted::pad_right`,
+// which must be at least the 40 bytes of i128::min_value().
+const FORMATTED_MAX_WIDTH: usize = 64;
+
+// The digits sit right after room for the most left padding any value can
+// need, and are followed by room for the most right padding.
+const FORMATTED_START: usize = FORMATTED_MAX_WIDTH - 1;
+const FORMATTED_LEN: usize = FORMATTED_START + FORMATTED_MAX_WIDTH;
+
+/// Integer formatted by [`itoa::to_array`](fn.to_array.html).
+///
+/// The bytes are stored in an array large enough for any integer plus
+/// padding, so the value can be moved around without borrowing a `Buffer`
+/// and padded to several widths without formatting the digits again.
+#[derive(Copy, Clone)]
+pub struct Formatted {
+    bytes: [u8; FORMATTED_LEN],
+    len: usize,
+}
+
+impl Formatted {
+    /// The formatted integer as a string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        ascii(self.as_bytes())
+    }
+
+    /// The formatted integer as bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[FORMATTED_START..FORMATTED_START + self.len]
+    }
+
+    /// The formatted integer left-padded with `fill` to at least `width`
+    /// characters.
+    ///
+    /// A value that is already at least `width` characters long is returned
+    /// unchanged. `width` is clamped to 64.
+    ///
+    /// ```
+    /// let mut formatted = itoa::to_array(-42i32);
+    /// assert_eq!(formatted.pad_left(5, b' '), "  -42");
+    /// assert_eq!(formatted.pad_right(5, b'.'), "-42..");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is not ASCII.
+    pub fn pad_left(&mut self, width: usize, fill: u8) -> &str {
+        assert!(fill.is_ascii(), "fill must be ASCII");
+        let pad = cmp::min(width, FORMATTED_MAX_WIDTH).saturating_sub(self.len);
+        let start = FORMATTED_START - pad;
+        let end = FORMATTED_START + self.len;
+        for b in &mut self.bytes[start..FORMATTED_START] {
+            *b = fill;
+        }
+        ascii(&self.bytes[start..end])
+    }
+
+    /// The formatted integer right-padded with `fill` to at least `width`
+    /// characters.
+    ///
+    /// A value that is already at least `width` characters long is returned
+    /// unchanged. `width` is clamped to 64.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is not ASCII.
+    pub fn pad_right(&mut self, width: usize, fill: u8) -> &str {
+        assert!(fill.is_ascii(), "fill must be ASCII");
+        let pad = cmp::min(width, FORMATTED_MAX_WIDTH).saturating_sub(self.len);
+        let digits_end = FORMATTED_START + self.len;
+        let end = digits_end + pad;
+        for b in &mut self.bytes[digits_end..end] {
+            *b = fill;
+        }
+        ascii(&self.bytes[FORMATTED_START..end])
+    }
+}
+
+/// Integer that is formatted once, up front, and displayed from the cached
+/// bytes afterward.
+///
+/// This is for values that are displayed many times, such as a log field
+/// rendered by several appenders. Width, fill and alignment of the format spec
+/// are applied to the cached string as for a `str`.
+///
+/// ```
+/// let cached = itoa::Cached::new(-42i32);
+/// assert_eq!(cached.to_string(), "-42");
+/// assert_eq!(format!("[{:>5}]", cached), "[  -42]");
+/// ```
+pub struct Cached<I: Integer> {
+    value: I,
+    bytes: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl<I: Integer + Copy> Copy for Cached<I> {}
+
+impl<I: Integer + Copy> Clone for Cached<I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<I: Integer + Copy> Cached<I> {
+    /// Format `value` and keep the result.
+    #[inline]
+    pub fn new(value: I) -> Self {
+        let mut buf = Buffer::new();
+        let s = buf.format(value);
+        let mut bytes = [0u8; MAX_LEN];
+        bytes[..s.len()].copy_from_slice(s.as_bytes());
+        Cached {
+            value,
+            bytes,
+            len: s.len(),
+        }
+    }
+}
+
+impl<I: Integer> Cached<I> {
+    /// The integer that was formatted.
+    #[inline]
+    pub fn get(&self) -> &I {
+        &self.value
+    }
+
+    /// The cached string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        ascii(&self.bytes[..self.len])
+    }
+}
+
+impl<I: Integer> AsRef<str> for Cached<I> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<I: Integer> fmt::Display for Cached<I> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl<I: Integer> fmt::Debug for Cached<I> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Wrapper that implements `Display` using itoa's formatting.
+///
+/// Width, fill, alignment and the `+` and `0` flags of the format spec are
+/// applied the same as for the primitive integer types.
+///
+/// `Debug` prints the same as `Display`, except that the alternate form
+/// `{:#?}` groups the digits in threes with commas.
+///
+/// ```
+/// let s = format!("[{:>6}]", itoa::Itoa(-42));
+/// assert_eq!(s, "[   -42]");
+///
+/// let s = format!("{:#?}", itoa::Itoa(1234567));
+/// assert_eq!(s, "1,234,567");
+/// ```
+#[derive(Copy, Clone)]
+pub struct Itoa<I>(pub I);
+
+impl<I: Integer + Copy> fmt::Display for Itoa<I> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_with_spec(f, self.0)
+    }
+}
+
+impl<I: Integer + Copy> fmt::Debug for Itoa<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            let mut buf = WideBuffer::new();
+            f.write_str(buf.format_grouped(self.0, b',', 3))
+        } else {
+            fmt_with_spec(f, self.0)
+        }
+    }
+}
+
+/// Method form of [`Buffer::format`], for calling on the value.
+///
+/// This is implemented for every [`Integer`] type, and can be used as a bound
+/// in generic code the same way.
+///
+/// ```
+/// use itoa::ToStr;
+///
+/// fn label<T: ToStr>(id: T, buf: &mut itoa::Buffer) -> &str {
+///     id.itoa_to_str(buf)
+/// }
+///
+/// let mut buf = itoa::Buffer::new();
+/// assert_eq!(label(42u64, &mut buf), "42");
+/// assert_eq!((-7i8).itoa_to_str(&mut buf), "-7");
+/// ```
+pub trait ToStr {
+    /// Print this integer into `buf` and return a reference to its string
+    /// representation within the buffer.
+    fn itoa_to_str<'b>(&self, buf: &'b mut Buffer) -> &'b str;
+}
+
+impl<I: Integer + Copy> ToStr for I {
+    #[inline]
+    fn itoa_to_str<'b>(&self, buf: &'b mut Buffer) -> &'b str {
+        buf.format(*self)
+    }
+}
+
+/// Error from the `try_` formatting methods of [`Buffer`] when the requested
+/// width does not fit in the buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CapacityError {
+    needed: usize,
+    capacity: usize,
+}
+
+impl CapacityError {
+    /// Number of bytes the requested width would have needed.
+    #[inline]
+    pub fn needed(&self) -> usize {
+        self.needed
+    }
+
+    /// Number of bytes available, which is the capacity of a [`Buffer`].
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "width of {} exceeds buffer capacity of {}",
+            self.needed, self.capacity
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+#[inline]
+fn check_capacity(width: usize) -> Result<(), CapacityError> {
+    if width > BUFFER_LEN {
+        Err(CapacityError {
+            needed: width,
+            capacity: BUFFER_LEN,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// `fmt::Write` adapter that counts the bytes written through it.
+///
+/// ```
+/// let mut writer = itoa::CountingWriter::new(String::new());
+/// itoa::fmt(&mut writer, -42).unwrap();
+/// itoa::fmt(&mut writer, 7u8).unwrap();
+/// assert_eq!(writer.count(), 4);
+/// assert_eq!(writer.into_inner(), "-427");
+/// ```
+#[derive(Clone, Debug)]
+pub struct CountingWriter<W> {
+    inner: W,
+    count: usize,
+}
+
+impl<W> CountingWriter<W> {
+    /// Wrap a writer, starting the count at zero.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        CountingWriter { inner, count: 0 }
+    }
+
+    /// Number of bytes successfully written so far.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Borrow the wrapped writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwrap the writer, discarding the count.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for CountingWriter<W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_str(s)?;
+        self.count += s.len();
+        Ok(())
+    }
+}
+
+/// `fmt::Write` adapter that writes into a byte slice, for use without an
+/// allocator.
+///
+/// A write that does not fit in the remaining space returns `fmt::Error`
+/// without writing any of its bytes, so the written prefix is always valid
+/// UTF-8.
+///
+/// ```
+/// let mut bytes = [0u8; 8];
+/// let mut writer = itoa::SliceWriter::new(&mut bytes);
+/// itoa::fmt(&mut writer, -42).unwrap();
+/// assert_eq!(writer.written(), b"-42");
+/// ```
+#[derive(Debug)]
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Wrap a byte slice, starting to write at its beginning.
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter { buf, pos: 0 }
+    }
+
+    /// The bytes written so far.
+    #[inline]
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+}
+
+impl<'a> fmt::Write for SliceWriter<'a> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.pos + s.len();
+        if end > self.buf.len() {
+            return Err(fmt::Error);
+        }
+        self.buf[self.pos..end].copy_from_slice(s.as_bytes());
+        self.pos = end;
+        Ok(())
+    }
+}
+
+/// Write integer to a `fmt::Formatter`, respecting its format spec.
+///
+/// Width, fill, alignment and the `+` and `0` flags are applied the same as
+/// for the primitive integer types. This is meant to be called from `Display`
+/// impls that wrap an integer.
+///
+/// ```
+/// use std::fmt;
+///
+/// struct Meters(u32);
+///
+/// impl fmt::Display for Meters {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         itoa::fmt_with_spec(f, self.0)
+///     }
+/// }
+///
+/// assert_eq!(format!("{:>5}", Meters(42)), "   42");
+/// ```
+#[inline]
+pub fn fmt_with_spec<V: Integer>(f: &mut fmt::Formatter, value: V) -> fmt::Result {
+    let mut buf = Buffer::new();
+    let s = buf.format(value);
+    if s.as_bytes()[0] == b'-' {
+        f.pad_integral(false, "", &s[1..])
+    } else {
+        f.pad_integral(true, "", s)
+    }
+}
+
+/// A safe API for formatting integers to text.
+///
+/// The buffer has room for the 40 bytes of `i128::MIN` in base 10. Other
+/// radixes, fixed-point and accounting formats, and grouping of 128-bit
+/// integers can print more than that, and are methods of [`WideBuffer`]
+/// instead.
+///
+/// # Example
+///
+/// ```
+/// let mut buffer = itoa::Buffer::new();
+/// let printed = buffer.format(1234);
+/// assert_eq!(printed, "1234");
+/// ```
+#[derive(Copy)]
+pub struct Buffer {
+    bytes: [BufferByte; BUFFER_LEN],
+    // Length of the most recent output, which always ends at the end of
+    // `bytes`, or 0 if nothing has been formatted yet. Both lengths are at
+    // most BUFFER_LEN, so a byte each keeps the buffer close to its 40 bytes.
+    len: u8,
+    // Length of the content built up by `format_appending`, which always
+    // starts at the beginning of `bytes`.
+    appended: u8,
+}
+
+impl Default for Buffer {
+    #[inline]
+    fn default() -> Buffer {
+        Buffer::new()
+    }
+}
+
+/// A clone is empty, the same as [`Buffer::new`], so its
+/// [`last`](struct.Buffer.html#method.last) is `None` and it only compares
+/// equal to the original if nothing has been printed into that either. Copy
+/// the buffer with `*buffer` to keep its output.
+#[allow(clippy::non_canonical_clone_impl)] // no need to copy the uninitialized bytes
+impl Clone for Buffer {
+    #[inline]
+    fn clone(&self) -> Self {
+        Buffer::new()
+    }
+}
+
+/// Shows the string returned by [`Buffer::last`], or nothing if the buffer has
+/// not been printed into.
+///
+/// ```
+/// let mut buffer = itoa::Buffer::new();
+/// buffer.format(-42);
+/// assert_eq!(format!("[{}]", buffer), "[-42]");
+/// ```
+impl fmt::Display for Buffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.last().unwrap_or(""))
+    }
+}
+
+/// Appends to the content built up by
+/// [`Buffer::format_appending`](struct.Buffer.html#method.format_appending).
+///
+/// A write that does not fit in the remaining capacity returns `fmt::Error`
+/// without writing any of its bytes. Earlier writes that are part of the same
+/// `write!` are kept.
+impl fmt::Write for Buffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.append(s) {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
+/// Buffers compare by the string returned by [`Buffer::last`], with buffers
+/// that have not been printed into comparing equal to each other.
+impl PartialEq for Buffer {
+    fn eq(&self, other: &Buffer) -> bool {
+        self.last() == other.last()
+    }
+}
+
+impl Eq for Buffer {}
+
+impl hash::Hash for Buffer {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.last().hash(state);
+    }
+}
+
+impl fmt::Debug for Buffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.last() {
+            Some(s) => f.debug_tuple("Buffer").field(&s).finish(),
+            None => f.write_str("Buffer(<uninit>)"),
+        }
+    }
+}
+
+impl Buffer {
+    /// This is a cheap operation; you don't need to worry about reusing buffers
+    /// for efficiency.
+    #[inline]
+    pub fn new() -> Buffer {
+        Buffer {
+            bytes: [BUFFER_BYTE_INIT; BUFFER_LEN],
+            len: 0,
+            appended: 0,
+        }
+    }
+
+    /// Print an integer into this buffer and return a reference to its string representation
+    /// within the buffer.
+    pub fn format<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        self.finish(len)
+    }
+
+    /// Print a `u128` into this buffer and return a reference to its string
+    /// representation within the buffer, using the 64-bit code path when the
+    /// value fits in a `u64`.
+    ///
+    /// This checks `n <= u64::MAX` up front and only falls back to 128-bit
+    /// division for larger values. [`format`](#method.format) makes the same
+    /// check internally, so the output is identical; this makes the dispatch
+    /// explicit for callers that mostly hold small values.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_u128_narrow(42), "42");
+    /// ```
+    #[cfg(feature = "i128")]
+    #[inline]
+    pub fn format_u128_narrow(&mut self, n: u128) -> &str {
+        if n <= <u64>::max_value() as u128 {
+            self.format(n as u64)
+        } else {
+            self.format(n)
+        }
+    }
+
+    /// Print an integer into this buffer and return a reference to its string
+    /// representation within the buffer, along with whether the integer is
+    /// negative.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_detailed(-42), ("-42", true));
+    /// assert_eq!(buffer.format_detailed(0), ("0", false));
+    /// ```
+    pub fn format_detailed<I: Integer>(&mut self, i: I) -> (&str, bool) {
+        let len = i.write_end(&mut self.bytes).len();
+        let is_negative = get_byte(&self.bytes, BUFFER_LEN - len) == b'-';
+        (self.finish(len), is_negative)
+    }
+
+    /// Print an integer into this buffer left-padded with `'0'` to at least
+    /// `min_width` characters and return a reference to its string
+    /// representation within the buffer.
+    ///
+    /// The sign of a negative value goes in front of the zeros, so `-42` with
+    /// a `min_width` of 5 prints as `-0042`. A value that is already at least
+    /// `min_width` characters long is printed unchanged. `min_width` is
+    /// clamped to the capacity of the buffer, which is 40 bytes.
+    pub fn format_padded<I: Integer>(&mut self, i: I, min_width: usize) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        self.zero_pad(len, cmp::min(min_width, BUFFER_LEN))
+    }
+
+    /// Print an integer into this buffer with its magnitude left-padded with
+    /// `'0'` to the most digits any value of its type has, and return a
+    /// reference to its string representation within the buffer.
+    ///
+    /// Every non-negative value of a type prints with the same width, such as
+    /// 10 digits for `u32`. A negative value has its `-` in front of the
+    /// zeros.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_full_width(42u16), "00042");
+    /// assert_eq!(buffer.format_full_width(-42i16), "-00042");
+    /// ```
+    pub fn format_full_width<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        let is_negative = get_byte(&self.bytes, BUFFER_LEN - len) == b'-';
+        let width = I::MAX_STR.len() + is_negative as usize;
+        self.zero_pad(len, width)
+    }
+
+    // Pad the last `len` bytes of the buffer with zeros to `min_width`, after
+    // the sign if there is one. `min_width` must be at most BUFFER_LEN.
+    fn zero_pad(&mut self, len: usize, min_width: usize) -> &str {
+        if len >= min_width {
+            return self.finish(len);
+        }
+
+        let curr = BUFFER_LEN - len;
+        let target = BUFFER_LEN - min_width;
+        fill_bytes(&mut self.bytes[target..curr], b'0');
+        if get_byte(&self.bytes, curr) == b'-' {
+            set_byte(&mut self.bytes, curr, b'0');
+            set_byte(&mut self.bytes, target, b'-');
+        }
+        self.finish(min_width)
+    }
+
+    /// Print an integer into this buffer left-padded with `'0'` to at least
+    /// `min_width` characters, the same as
+    /// [`format_padded`](#method.format_padded), or return an error if
+    /// `min_width` is more than the capacity of the buffer.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.try_format_padded(-42, 5), Ok("-0042"));
+    ///
+    /// let err = buffer.try_format_padded(-42, 500).unwrap_err();
+    /// assert_eq!((err.needed(), err.capacity()), (500, 40));
+    /// ```
+    pub fn try_format_padded<I: Integer>(
+        &mut self,
+        i: I,
+        min_width: usize,
+    ) -> Result<&str, CapacityError> {
+        check_capacity(min_width)?;
+        Ok(self.format_padded(i, min_width))
+    }
+
+    /// Print an integer into this buffer left-padded with `fill` to at least
+    /// `width` characters and return a reference to its string representation
+    /// within the buffer.
+    ///
+    /// Unlike [`format_padded`](#method.format_padded), the fill goes in front
+    /// of the sign, so `-42` with a space fill and a `width` of 5 prints as
+    /// `  -42`. A value that is already at least `width` characters long is
+    /// printed unchanged. `width` is clamped to the capacity of the buffer,
+    /// which is 40 bytes.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_right_aligned(-42, 5, b' '), "  -42");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is not ASCII.
+    pub fn format_right_aligned<I: Integer>(&mut self, i: I, width: usize, fill: u8) -> &str {
+        assert!(fill.is_ascii(), "fill must be ASCII");
+        let len = i.write_end(&mut self.bytes).len();
+        let width = cmp::min(width, BUFFER_LEN);
+        if len >= width {
+            return self.finish(len);
+        }
+
+        fill_bytes(&mut self.bytes[BUFFER_LEN - width..BUFFER_LEN - len], fill);
+        self.finish(width)
+    }
+
+    /// Print an integer into this buffer right-aligned with spaces in a field
+    /// of exactly `width` characters and return a reference to its string
+    /// representation within the buffer.
+    ///
+    /// A value too wide for the field prints as `width` asterisks instead, so
+    /// a column of fields never loses its alignment. `width` is clamped to the
+    /// capacity of the buffer, which is 40 bytes.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_field(-42, 5), "  -42");
+    /// assert_eq!(buffer.format_field(123456, 5), "*****");
+    /// ```
+    pub fn format_field<I: Integer>(&mut self, i: I, width: usize) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        let width = cmp::min(width, BUFFER_LEN);
+        if len > width {
+            fill_bytes(&mut self.bytes[BUFFER_LEN - width..], b'*');
+        } else {
+            fill_bytes(&mut self.bytes[BUFFER_LEN - width..BUFFER_LEN - len], b' ');
+        }
+        self.finish(width)
+    }
+
+    /// Print an integer into this buffer left-padded with `fill` to at least
+    /// `width` characters, the same as
+    /// [`format_right_aligned`](#method.format_right_aligned), or return an
+    /// error if `width` is more than the capacity of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill` is not ASCII.
+    pub fn try_format_right_aligned<I: Integer>(
+        &mut self,
+        i: I,
+        width: usize,
+        fill: u8,
+    ) -> Result<&str, CapacityError> {
+        check_capacity(width)?;
+        Ok(self.format_right_aligned(i, width, fill))
+    }
+
+    /// Print an integer into this buffer with an explicit sign and return a
+    /// reference to its string representation within the buffer.
+    ///
+    /// Values greater than or equal to zero get a leading `+`, so `0` prints
+    /// as `+0` and `5` prints as `+5`. Negative values print the same as
+    /// [`format`](#method.format).
+    pub fn format_signed<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        let curr = BUFFER_LEN - len;
+        if get_byte(&self.bytes, curr) == b'-' {
+            self.finish(len)
+        } else {
+            set_byte(&mut self.bytes, curr - 1, b'+');
+            self.finish(len + 1)
+        }
+    }
+
+    /// Print the magnitude of an integer into this buffer without a sign and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// This does not overflow for the minimum value of a signed type, so
+    /// `i64::MIN` prints as `9223372036854775808`.
+    pub fn format_abs<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.unsigned_abs().write_end(&mut self.bytes).len();
+        self.finish(len)
+    }
+
+    /// Print an integer into this buffer with its digits least significant
+    /// first and return a reference to its string representation within the
+    /// buffer.
+    ///
+    /// The `-` of a negative value stays in front of the digits, so `-120`
+    /// prints as `-021`. For non-negative values the result is the reverse of
+    /// [`format`](#method.format).
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_reversed(120), "021");
+    /// ```
+    pub fn format_reversed<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        let mut curr = BUFFER_LEN - len;
+        if get_byte(&self.bytes, curr) == b'-' {
+            curr += 1;
+        }
+        self.bytes[curr..].reverse();
+        self.finish(len)
+    }
+
+    /// Print an integer of at most 64 bits into this buffer with `separator`
+    /// inserted between every `group_size` digits counting from the right, and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// The sign of a negative value stays in front, so `-1234567` with a `,`
+    /// separator and a `group_size` of 3 prints as `-1,234,567`. The widest
+    /// output is `u64::MAX` with a `group_size` of 1 at 39 bytes, so this
+    /// stays in the 40 bytes of the buffer. Grouping `i128` and `u128` needs
+    /// [`WideBuffer::format_grouped`] or the sink-based
+    /// [`itoa::fmt_grouped`](fn.fmt_grouped.html).
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_grouped(-1234567, b',', 3), "-1,234,567");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `I` is wider than 64 bits, `separator` is not ASCII or
+    /// `group_size` is zero.
+    pub fn format_grouped<I: Integer>(&mut self, i: I, separator: u8, group_size: usize) -> &str {
+        assert!(
+            I::BITS <= 64,
+            "use WideBuffer to group integers wider than 64 bits"
+        );
+        assert!(separator.is_ascii(), "separator must be ASCII");
+        assert!(group_size != 0, "group_size must be nonzero");
+        let len = i.write_end(&mut self.bytes).len();
+        let grouped_len = group_digits(&mut self.bytes, len, separator, group_size, group_size);
+        self.finish(grouped_len)
+    }
+
+    /// Print an integer into this buffer using `minus` in place of `-` as the
+    /// sign of negative values, and return a reference to its string
+    /// representation within the buffer.
+    ///
+    /// If `plus` is given, values greater than or equal to zero get it as a
+    /// leading sign the same way [`format_signed`](#method.format_signed)
+    /// adds `+`. Signs that wrap the digits, such as the parentheses used in
+    /// accounting, are not supported here.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_with_sign(-5, b'~', None), "~5");
+    /// assert_eq!(buffer.format_with_sign(5, b'~', Some(b' ')), " 5");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `minus` or `plus` is not ASCII.
+    pub fn format_with_sign<I: Integer>(&mut self, i: I, minus: u8, plus: Option<u8>) -> &str {
+        assert!(minus.is_ascii(), "minus must be ASCII");
+        if let Some(plus) = plus {
+            assert!(plus.is_ascii(), "plus must be ASCII");
+        }
+        let len = i.write_end(&mut self.bytes).len();
+        let curr = BUFFER_LEN - len;
+        if get_byte(&self.bytes, curr) == b'-' {
+            set_byte(&mut self.bytes, curr, minus);
+            self.finish(len)
+        } else if let Some(plus) = plus {
+            set_byte(&mut self.bytes, curr - 1, plus);
+            self.finish(len + 1)
+        } else {
+            self.finish(len)
+        }
+    }
+
+    /// Print an integer into this buffer zero-padded to the full width of its
+    /// type and return a reference to its string representation within the
+    /// buffer.
+    ///
+    /// Every digit position of the type's largest value is written, so `42u16`
+    /// prints as `00042` and `-42i16` prints as `-00042`. The number of loop
+    /// iterations and the branches taken do not depend on the value, which
+    /// avoids leaking its magnitude through timing. Only the length of the
+    /// output depends on the sign. 128-bit values are first split into
+    /// 19-digit chunks by a long division with a fixed number of steps, so
+    /// that only `u64` is divided by 10. This relies on the compiler turning
+    /// that division into multiplication, which is not guaranteed on every
+    /// target.
+    pub fn format_padded_constant_time<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_constant_time(&mut self.bytes).len();
+        self.finish(len)
+    }
+
+    /// Print an integer into this buffer in compact exponent form if that is
+    /// shorter, and return a reference to its string representation within
+    /// the buffer.
+    ///
+    /// The trailing zeros of the value become an exponent, so `1200000` prints
+    /// as `12e5`. The plain form from [`format`](#method.format) is kept when
+    /// the exponent form would not be strictly shorter, which is the case for
+    /// `100` and every other value with fewer than 3 trailing zeros.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_compact(1000000), "1e6");
+    /// assert_eq!(buffer.format_compact(-1200000), "-12e5");
+    /// assert_eq!(buffer.format_compact(100), "100");
+    /// ```
+    pub fn format_compact<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        let mut zeros = 0;
+        while zeros + 1 < len && get_byte(&self.bytes, BUFFER_LEN - 1 - zeros) == b'0' {
+            zeros += 1;
+        }
+        let exp_len = if zeros < 10 { 1 } else { 2 };
+        if 1 + exp_len >= zeros {
+            return self.finish(len);
+        }
+        // Shift the mantissa right over the zeros that are not replaced by the
+        // exponent.
+        let mantissa = len - zeros;
+        let compact_len = mantissa + 1 + exp_len;
+        move_bytes(
+            &mut self.bytes,
+            BUFFER_LEN - len,
+            BUFFER_LEN - compact_len,
+            mantissa,
+        );
+        set_byte(&mut self.bytes, BUFFER_LEN - 1 - exp_len, b'e');
+        if exp_len == 2 {
+            set_byte(&mut self.bytes, BUFFER_LEN - 2, b'0' + (zeros / 10) as u8);
+        }
+        set_byte(&mut self.bytes, BUFFER_LEN - 1, b'0' + (zeros % 10) as u8);
+        self.finish(compact_len)
+    }
+
+    /// Print a number of seconds into this buffer as hours, minutes and
+    /// seconds separated by `:`, and return a reference to its string
+    /// representation within the buffer.
+    ///
+    /// Each field is at least two digits. The hours are not limited to 24 or
+    /// to two digits, so every `u64` has a representation.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.format_clock(3661), "01:01:01");
+    /// assert_eq!(buffer.format_clock(360000), "100:00:00");
+    /// ```
+    pub fn format_clock(&mut self, total_seconds: u64) -> &str {
+        let hours = total_seconds / 3600;
+        let minutes = (total_seconds / 60 % 60) as usize;
+        let seconds = (total_seconds % 60) as usize;
+        let digits = hours.write_end(&mut self.bytes).len();
+        if digits == 1 {
+            set_byte(&mut self.bytes, BUFFER_LEN - 2, b'0');
+        }
+        // Shift the hours left to make room for the minutes and seconds.
+        let len = cmp::max(digits, 2);
+        let curr = BUFFER_LEN - len;
+        move_bytes(&mut self.bytes, curr, curr - 6, len);
+        set_byte(&mut self.bytes, BUFFER_LEN - 6, b':');
+        copy_from(
+            &mut self.bytes[BUFFER_LEN - 5..],
+            &DEC_DIGITS_LUT[minutes * 2..minutes * 2 + 2],
+        );
+        set_byte(&mut self.bytes, BUFFER_LEN - 3, b':');
+        copy_from(
+            &mut self.bytes[BUFFER_LEN - 2..],
+            &DEC_DIGITS_LUT[seconds * 2..seconds * 2 + 2],
+        );
+        self.finish(len + 6)
+    }
+
+    /// Number of bytes this buffer holds, which is the base 10 capacity
+    /// `i128::MAX_STR_LEN`: the length of `i128::MIN`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        BUFFER_LEN
+    }
+
+    /// Whether this buffer holds no output, meaning that [`last`](#method.last)
+    /// returns `None` and nothing has been appended by
+    /// [`format_appending`](#method.format_appending).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0 && self.appended == 0
+    }
+
+    /// Return the string most recently printed into this buffer by any of the
+    /// `format` methods, or `None` if nothing has been printed since the
+    /// buffer was created or cloned.
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// assert_eq!(buffer.last(), None);
+    /// buffer.format(-42);
+    /// assert_eq!(buffer.last(), Some("-42"));
+    /// ```
+    #[inline]
+    pub fn last(&self) -> Option<&str> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.tail(self.len as usize))
+        }
+    }
+
+    /// Print an integer after everything previously appended to this buffer
+    /// and return a reference to all of the appended content.
+    ///
+    /// Returns `None` without modifying the buffer if the integer does not fit
+    /// in the remaining capacity, which is 40 bytes in total. Text written
+    /// through the buffer's `fmt::Write` impl is appended to the same content.
+    /// Calling any of the other `format` methods discards the appended content,
+    /// and so does [`reset`](#method.reset).
+    ///
+    /// ```
+    /// let mut buffer = itoa::Buffer::new();
+    /// buffer.format_appending(12);
+    /// buffer.format_appending(-3);
+    /// assert_eq!(buffer.format_appending(45), Some("12-345"));
+    /// ```
+    pub fn format_appending<I: Integer>(&mut self, i: I) -> Option<&str> {
+        let mut scratch = Buffer::new();
+        let s = scratch.format(i);
+        if self.append(s) {
+            Some(self.as_str())
+        } else {
+            None
+        }
+    }
+
+    /// Return the content built up by
+    /// [`format_appending`](#method.format_appending) and the buffer's
+    /// `fmt::Write` impl, which is empty if nothing has been appended.
+    ///
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// let mut buffer = itoa::Buffer::new();
+    /// write!(buffer, "[{}]", 42).unwrap();
+    /// assert_eq!(buffer.as_str(), "[42]");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        buffer_ascii(&self.bytes[..self.appended as usize])
+    }
+
+    /// Discard the content built up by
+    /// [`format_appending`](#method.format_appending) so that the next call
+    /// starts again at the beginning of the buffer.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.appended = 0;
+    }
+
+    // Copy `s` after the appended content, or return false without modifying
+    // the buffer if it does not fit.
+    fn append(&mut self, s: &str) -> bool {
+        let appended = self.appended as usize;
+        if s.len() > BUFFER_LEN - appended {
+            return false;
+        }
+        copy_from(&mut self.bytes[appended..], s.as_bytes());
+        // The copy may have overwritten the output of an earlier `format`.
+        self.len = 0;
+        self.appended = (appended + s.len()) as u8;
+        true
+    }
+
+    // Record the last `len` bytes of the buffer as the most recent output.
+    #[inline]
+    fn finish(&mut self, len: usize) -> &str {
+        self.len = len as u8;
+        self.appended = 0;
+        self.tail(len)
+    }
+
+    // The last `len` bytes of the buffer, which must have been written.
+    #[inline]
+    fn tail(&self, len: usize) -> &str {
+        buffer_ascii(&self.bytes[BUFFER_LEN - len..])
+    }
+}
+
+/// A buffer for the formats whose output can be longer than any integer in
+/// base 10.
+///
+/// [`Buffer`] only has room for the 40 bytes of `i128::MIN` in base 10, so that
+/// formatting with it, or with `itoa::fmt` and `itoa::write`, takes up as
+/// little stack space as possible. This buffer holds 130 bytes, enough for
+/// `u128::MAX` in base 2 after a `0b` prefix, and has the methods for other
+/// radixes, digit grouping, accounting style and fixed-point numbers, all of
+/// which can print more than 40 bytes for a 128-bit integer.
+///
+/// ```
+/// let mut buffer = itoa::WideBuffer::new();
+/// assert_eq!(buffer.format_binary(5u8), "101");
+/// ```
+#[derive(Copy)]
+pub struct WideBuffer {
+    bytes: [BufferByte; WIDE_BUFFER_LEN],
+}
+
+impl Default for WideBuffer {
+    #[inline]
+    fn default() -> WideBuffer {
+        WideBuffer::new()
+    }
+}
+
+#[allow(clippy::non_canonical_clone_impl)] // no need to copy the uninitialized bytes
+impl Clone for WideBuffer {
+    #[inline]
+    fn clone(&self) -> Self {
+        WideBuffer::new()
+    }
+}
+
+impl WideBuffer {
+    /// This is a cheap operation; you don't need to worry about reusing buffers
+    /// for efficiency.
+    #[inline]
+    pub fn new() -> WideBuffer {
+        WideBuffer {
+            bytes: [BUFFER_BYTE_INIT; WIDE_BUFFER_LEN],
+        }
+    }
+
+    /// Print an integer into this buffer in base 2 and return a reference to
+    /// its string representation within the buffer.
+    ///
+    /// Negative values are printed as their two's complement bit pattern, the
+    /// same as `{:b}` does, so `-1i8` prints as `11111111`.
+    pub fn format_binary<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_pow2(&mut self.bytes, 1).len();
+        self.finish(len)
+    }
+
+    /// Print an integer into this buffer in base 8 and return a reference to
+    /// its string representation within the buffer.
+    ///
+    /// Negative values are printed as their two's complement bit pattern, the
+    /// same as `{:o}` does, so `-1i8` prints as `377`.
+    pub fn format_octal<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_pow2(&mut self.bytes, 3).len();
+        self.finish(len)
+    }
+
+    /// Print an integer into this buffer in base 16 after a `0x` prefix and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// Negative values are printed as their two's complement bit pattern with
+    /// no sign, the same as `{:#x}` does, so `-1i8` prints as `0xff`.
+    pub fn format_hex_prefixed<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_pow2(&mut self.bytes, 4).len();
+        self.prefix(len, b'x')
+    }
+
+    /// Print an integer into this buffer in base 16, left-padded with `'0'` to
+    /// two digits per byte of the type, and return a reference to its string
+    /// representation within the buffer.
+    ///
+    /// Negative values are printed as their two's complement bit pattern, so
+    /// every value of a type prints with the same width, such as 8 digits for
+    /// `u32` and `i32`.
+    ///
+    /// ```
+    /// let mut buffer = itoa::WideBuffer::new();
+    /// assert_eq!(buffer.format_hex_padded(5u8), "05");
+    /// assert_eq!(buffer.format_hex_padded(255u16), "00ff");
+    /// ```
+    pub fn format_hex_padded<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_pow2(&mut self.bytes, 4).len();
+        let width = I::BITS as usize / 4;
+        fill_bytes(
+            &mut self.bytes[WIDE_BUFFER_LEN - width..WIDE_BUFFER_LEN - len],
+            b'0',
+        );
+        self.finish(width)
+    }
+
+    /// Print an integer into this buffer in base 2 after a `0b` prefix and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// Negative values are printed as their two's complement bit pattern with
+    /// no sign, the same as `{:#b}` does.
+    pub fn format_binary_prefixed<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_pow2(&mut self.bytes, 1).len();
+        self.prefix(len, b'b')
+    }
+
+    /// Print an integer into this buffer in base 8 after a `0o` prefix and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// Negative values are printed as their two's complement bit pattern with
+    /// no sign, the same as `{:#o}` does.
+    pub fn format_octal_prefixed<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_pow2(&mut self.bytes, 3).len();
+        self.prefix(len, b'o')
+    }
+
+    // Put `0` and `letter` in front of the last `len` bytes of the buffer.
+    fn prefix(&mut self, len: usize, letter: u8) -> &str {
+        let curr = WIDE_BUFFER_LEN - len;
+        set_byte(&mut self.bytes, curr - 2, b'0');
+        set_byte(&mut self.bytes, curr - 1, letter);
+        self.finish(len + 2)
+    }
+
+    /// Print an integer into this buffer in the given radix and return a
+    /// reference to its string representation within the buffer.
+    ///
+    /// Digits above 9 are the lowercase letters `a` through `z`. Negative
+    /// values are printed as a `-` followed by the magnitude, the same as
+    /// [`Buffer::format`](struct.Buffer.html#method.format) does in base 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range `2..=36`.
+    pub fn format_radix<I: Integer>(&mut self, i: I, radix: u32) -> &str {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36, got {}",
+            radix
+        );
+        let len = i.write_radix(&mut self.bytes, radix).len();
+        self.finish(len)
+    }
+
+    /// Print an integer into this buffer in base 36 with lowercase letters and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// This is the same as [`format_radix`](#method.format_radix) with a radix
+    /// of 36, which makes for short identifiers.
+    ///
+    /// ```
+    /// let mut buffer = itoa::WideBuffer::new();
+    /// assert_eq!(buffer.format_base36(1_000_000u32), "lfls");
+    /// ```
+    pub fn format_base36<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_radix(&mut self.bytes, 36).len();
+        self.finish(len)
+    }
+
+    /// Print an integer into this buffer in base 36 with uppercase letters and
+    /// return a reference to its string representation within the buffer.
+    ///
+    /// ```
+    /// let mut buffer = itoa::WideBuffer::new();
+    /// assert_eq!(buffer.format_base36_upper(1_000_000u32), "LFLS");
+    /// ```
+    pub fn format_base36_upper<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_radix(&mut self.bytes, 36).len();
+        for k in WIDE_BUFFER_LEN - len..WIDE_BUFFER_LEN {
+            let upper = get_byte(&self.bytes, k).to_ascii_uppercase();
+            set_byte(&mut self.bytes, k, upper);
+        }
+        self.finish(len)
+    }
+
+    /// Print an integer into this buffer as a fixed-point number with
+    /// `fractional_digits` digits after the decimal point, and return a
+    /// reference to its string representation within the buffer.
+    ///
+    /// This is meant for values stored as an integer count of a fraction of a
+    /// unit, such as cents. A magnitude with too few digits gets leading zeros,
+    /// so `5` with 2 fractional digits prints as `0.05` and `-5` prints as
+    /// `-0.05`. With 0 fractional digits there is no decimal point.
+    /// `fractional_digits` is clamped to 127, which is as many as fit in the
+    /// buffer after a sign, a leading `0` and the point.
+    ///
+    /// ```
+    /// let mut buffer = itoa::WideBuffer::new();
+    /// assert_eq!(buffer.format_fixed_point(12345, 2), "123.45");
+    /// ```
+    pub fn format_fixed_point<I: Integer>(&mut self, i: I, fractional_digits: usize) -> &str {
+        let fractional_digits = cmp::min(fractional_digits, WIDE_BUFFER_LEN - 3);
+        let len = i.write_end(&mut self.bytes).len();
+        if fractional_digits == 0 {
+            return self.finish(len);
+        }
+
+        let curr = WIDE_BUFFER_LEN - len;
+        let is_nonnegative = get_byte(&self.bytes, curr) != b'-';
+        let digits = if is_nonnegative { len } else { len - 1 };
+        let point = WIDE_BUFFER_LEN - fractional_digits - 1;
+        let start = if digits > fractional_digits {
+            // Shift the integer part and the sign left to make room for the
+            // point.
+            move_bytes(&mut self.bytes, curr, curr - 1, point + 1 - curr);
+            curr - 1
+        } else {
+            let zeros = fractional_digits - digits;
+            fill_bytes(&mut self.bytes[point + 1..point + 1 + zeros], b'0');
+            set_byte(&mut self.bytes, point - 1, b'0');
+            if is_nonnegative {
+                point - 1
+            } else {
+                set_byte(&mut self.bytes, point - 2, b'-');
+                point - 2
+            }
+        };
+        set_byte(&mut self.bytes, point, b'.');
+        self.finish(WIDE_BUFFER_LEN - start)
+    }
+
+    /// Print an integer into this buffer in accounting style and return a
+    /// reference to its string representation within the buffer.
+    ///
+    /// Negative values are wrapped in parentheses instead of getting a `-`,
+    /// so `-42` prints as `(42)`. Other values print the same as
+    /// [`Buffer::format`](struct.Buffer.html#method.format).
+    ///
+    /// ```
+    /// let mut buffer = itoa::WideBuffer::new();
+    /// assert_eq!(buffer.format_accounting(-1500), "(1500)");
+    /// ```
+    pub fn format_accounting<I: Integer>(&mut self, i: I) -> &str {
+        let len = i.write_end(&mut self.bytes).len();
+        let curr = WIDE_BUFFER_LEN - len;
+        if get_byte(&self.bytes, curr) != b'-' {
+            return self.finish(len);
+        }
+        // Shift the digits left over the `-` to make room for the `)`.
+        let digits = len - 1;
+        move_bytes(&mut self.bytes, curr + 1, curr, digits);
+        set_byte(&mut self.bytes, curr - 1, b'(');
+        set_byte(&mut self.bytes, WIDE_BUFFER_LEN - 1, b')');
+        self.finish(len + 1)
+    }
+
+    /// Print an integer into this buffer with `separator` inserted between
+    /// every `group_size` digits counting from the right, and return a
+    /// reference to its string representation within the buffer.
+    ///
+    /// The sign of a negative value stays in front, so `-1234567` with a `,`
+    /// separator and a `group_size` of 3 prints as `-1,234,567`.
+    /// The output is built in place without allocating, and fits in the buffer
+    /// for every integer type, including `i128::MIN` with a `group_size` of 1.
+    /// For types up to 64 bits, [`Buffer::format_grouped`] does the same in
+    /// the smaller buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `separator` is not ASCII or `group_size` is zero.
+    pub fn format_grouped<I: Integer>(&mut self, i: I, separator: u8, group_size: usize) -> &str {
+        assert!(separator.is_ascii(), "separator must be ASCII");
+        assert!(group_size != 0, "group_size must be nonzero");
+        let len = i.write_end(&mut self.bytes).len();
+        self.group(len, separator, group_size, group_size)
+    }
+
+    /// Print an integer into this buffer grouped according to the Indian
+    /// numbering system and return a reference to its string representation
+    /// within the buffer.
+    ///
+    /// The last three digits form one group and the digits before them are
+    /// grouped in twos, so `1234567` with a `,` separator prints as
+    /// `12,34,567`. The sign of a negative value stays in front.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `separator` is not ASCII.
+    pub fn format_grouped_indian<I: Integer>(&mut self, i: I, separator: u8) -> &str {
+        assert!(separator.is_ascii(), "separator must be ASCII");
+        let len = i.write_end(&mut self.bytes).len();
+        self.group(len, separator, 3, 2)
+    }
+
+    fn group(&mut self, len: usize, separator: u8, last: usize, size: usize) -> &str {
+        let grouped_len = group_digits(&mut self.bytes, len, separator, last, size);
+        debug_assert!(grouped_len <= GROUPED_MAX_LEN);
+        self.finish(grouped_len)
+    }
+
+    // The last `len` bytes of the buffer, which must have been written.
+    #[inline]
+    fn finish(&mut self, len: usize) -> &str {
+        buffer_ascii(&self.bytes[WIDE_BUFFER_LEN - len..])
+    }
+}
+
+// Insert separators into the last `len` bytes of `buf`, which hold a decimal
+// integer, and return the grouped length. The rightmost group has `last`
+// digits and every other group has `size` digits.
+fn group_digits(
+    buf: &mut [BufferByte],
+    len: usize,
+    separator: u8,
+    last: usize,
+    size: usize,
+) -> usize {
+    let is_nonnegative = get_byte(buf, buf.len() - len) != b'-';
+    let digits = if is_nonnegative { len } else { len - 1 };
+    let separators = if digits > last {
+        1 + (digits - last - 1) / size
+    } else {
+        0
+    };
+    let grouped_len = len + separators;
+
+    // Move the digits left one group at a time, which never overwrites
+    // digits that have not been moved yet.
+    let mut src = buf.len() - digits;
+    let mut dst = buf.len() - grouped_len;
+    if !is_nonnegative {
+        set_byte(buf, dst, b'-');
+        dst += 1;
+    }
+    let first = if separators == 0 {
+        digits
+    } else {
+        digits - last - (separators - 1) * size
+    };
+    move_bytes(buf, src, dst, first);
+    src += first;
+    dst += first;
+    while src < buf.len() {
+        let group = if buf.len() - src == last { last } else { size };
+        set_byte(buf, dst, separator);
+        dst += 1;
+        move_bytes(buf, src, dst, group);
+        src += group;
+        dst += group;
+    }
+
+    grouped_len
+}
+
+/// A buffer that only has room for one integer type, for formatting in base 10.
+///
+/// [`Buffer`] is large enough for every format of every integer type. This is
+/// sized to [`Integer::MAX_STR_LEN`] of `I` instead, so that it takes up as
+/// little stack space as possible.
+///
+/// ```
+/// use std::mem;
+///
+/// let mut buffer = itoa::TypedBuffer::<u16>::new();
+/// assert_eq!(buffer.format(65535), "65535");
+/// assert_eq!(mem::size_of_val(&buffer), 5);
+/// ```
+pub struct TypedBuffer<I: Integer> {
+    bytes: I::Storage,
+}
+
+impl<I: Integer> Copy for TypedBuffer<I> {}
+
+#[allow(clippy::non_canonical_clone_impl)] // no need to copy the uninitialized bytes
+impl<I: Integer> Clone for TypedBuffer<I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        TypedBuffer::new()
+    }
+}
+
+impl<I: Integer> Default for TypedBuffer<I> {
+    #[inline]
+    fn default() -> Self {
+        TypedBuffer::new()
+    }
+}
+
+impl<I: Integer> TypedBuffer<I> {
+    /// This is a cheap operation; you don't need to worry about reusing buffers
+    /// for efficiency.
+    #[inline]
+    pub fn new() -> Self {
+        TypedBuffer {
+            bytes: I::STORAGE_INIT,
+        }
+    }
+
+    /// Print an integer into this buffer and return a reference to its string
+    /// representation within the buffer.
+    #[inline]
+    pub fn format(&mut self, i: I) -> &str {
+        i.write_typed(&mut self.bytes)
+    }
+}
+
+// Without the safe feature the bytes of a Buffer are left uninitialized until
+// they are written. With it they start out zeroed, so that the base 10 path can
+// index into them without unsafe code.
+#[cfg(not(feature = "safe"))]
+type BufferByte = MaybeUninit<u8>;
+#[cfg(not(feature = "safe"))]
+const BUFFER_BYTE_INIT: BufferByte = MaybeUninit::uninit();
+#[cfg(feature = "safe")]
+type BufferByte = u8;
+#[cfg(feature = "safe")]
+const BUFFER_BYTE_INIT: BufferByte = 0;
+
+// Byte access shared by Buffer, WideBuffer and the Integer impls. Every write
+// is bounds checked. Without the safe feature, `get_byte` and `buffer_ascii`
+// must only be used on bytes that have already been written.
+#[cfg(not(feature = "safe"))]
+#[inline]
+fn byte(b: u8) -> BufferByte {
+    MaybeUninit::new(b)
+}
+
+#[cfg(feature = "safe")]
+#[inline]
+fn byte(b: u8) -> BufferByte {
+    b
+}
+
+#[cfg(not(feature = "safe"))]
+#[inline]
+fn get_byte(buf: &[BufferByte], i: usize) -> u8 {
+    unsafe { buf[i].assume_init() }
+}
+
+#[cfg(feature = "safe")]
+#[inline]
+fn get_byte(buf: &[BufferByte], i: usize) -> u8 {
+    buf[i]
+}
+
+#[cfg(not(feature = "safe"))]
+#[inline]
+fn buffer_ascii(buf: &[BufferByte]) -> &str {
+    unsafe { ascii(slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len())) }
+}
+
+#[cfg(feature = "safe")]
+#[inline]
+fn buffer_ascii(buf: &[BufferByte]) -> &str {
+    ascii(buf)
+}
+
+// Copy `src` into the start of `buf` and return the copied bytes, for
+// `write_uninit` with the safe feature.
+#[cfg(all(feature = "safe", not(no_maybe_uninit_write_slice)))]
+#[allow(clippy::incompatible_msrv)] // gated on no_maybe_uninit_write_slice
+#[inline]
+fn init_uninit<'a>(buf: &'a mut [MaybeUninit<u8>], src: &[u8]) -> &'a [u8] {
+    buf[..src.len()].write_copy_of_slice(src)
+}
+
+// Older compilers have no safe way to view the initialized bytes as `&[u8]`,
+// so this is the one unsafe conversion that the safe feature allows there.
+#[cfg(all(feature = "safe", no_maybe_uninit_write_slice))]
+#[allow(unsafe_code)]
+#[inline]
+fn init_uninit<'a>(buf: &'a mut [MaybeUninit<u8>], src: &[u8]) -> &'a [u8] {
+    let buf = &mut buf[..src.len()];
+    for (d, &b) in buf.iter_mut().zip(src) {
+        *d = MaybeUninit::new(b);
+    }
+    unsafe { slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len()) }
+}
+
+// The bytes written by this crate are always ASCII.
+#[cfg(not(feature = "safe"))]
+#[inline]
+fn ascii(bytes: &[u8]) -> &str {
+    unsafe { str::from_utf8_unchecked(bytes) }
+}
+
+#[cfg(feature = "safe")]
+#[inline]
+fn ascii(bytes: &[u8]) -> &str {
+    str::from_utf8(bytes).unwrap()
+}
+
+#[inline]
+fn set_byte(buf: &mut [BufferByte], i: usize, b: u8) {
+    buf[i] = byte(b);
+}
+
+#[inline]
+fn fill_bytes(buf: &mut [BufferByte], b: u8) {
+    for dst in buf {
+        *dst = byte(b);
+    }
+}
+
+#[inline]
+fn copy_from(buf: &mut [BufferByte], src: &[u8]) {
+    for (dst, &b) in buf.iter_mut().zip(src) {
+        *dst = byte(b);
+    }
+}
+
+// Copy `len` bytes from `src` to `dst` within `buf`, where the two ranges may
+// overlap. This is `copy_within`, which is newer than the minimum supported
+// compiler.
+#[inline]
+fn move_bytes(buf: &mut [BufferByte], src: usize, dst: usize, len: usize) {
+    if dst <= src {
+        for k in 0..len {
+            buf[dst + k] = buf[src + k];
+        }
+    } else {
+        for k in (0..len).rev() {
+            buf[dst + k] = buf[src + k];
+        }
+    }
+}
+
+// Seal to prevent downstream implementations of the Integer trait.
+mod private {
+    pub trait Sealed {}
+}
+
+/// An integer that can be formatted by `itoa::write` and `itoa::fmt`.
+///
+/// This trait is sealed and cannot be implemented for types outside of itoa.
+pub trait Integer: private::Sealed {
+    /// Length of the longest base 10 output of this type, including the sign.
+    ///
+    /// ```
+    /// use itoa::Integer;
+    ///
+    /// assert_eq!(i8::MAX_STR_LEN, "-128".len());
+    /// assert_eq!(u64::MAX_STR_LEN, "18446744073709551615".len());
+    /// ```
+    const MAX_STR_LEN: usize;
+
+    /// Whether this type can hold negative values.
+    ///
+    /// ```
+    /// use itoa::Integer;
+    ///
+    /// assert!(i32::SIGNED);
+    /// assert!(!usize::SIGNED);
+    /// ```
+    const SIGNED: bool;
+
+    /// Base 10 representation of the smallest value of this type.
+    ///
+    /// ```
+    /// use itoa::Integer;
+    ///
+    /// assert_eq!(i16::MIN_STR, "-32768");
+    /// assert_eq!(u16::MIN_STR, "0");
+    /// ```
+    const MIN_STR: &'static str;
+
+    /// Base 10 representation of the largest value of this type.
+    ///
+    /// ```
+    /// use itoa::Integer;
+    ///
+    /// assert_eq!(i16::MAX_STR, "32767");
+    /// assert_eq!(u16::MAX_STR, "65535");
+    /// ```
+    const MAX_STR: &'static str;
+
+    /// Unsigned type of the same width, which can hold the magnitude of every
+    /// value of this type.
+    ///
+    /// This is the type itself for unsigned types. Wrappers such as
+    /// `NonZeroI8` use the unsigned type of the integer they wrap, here `u8`.
+    type Unsigned: Integer + Copy;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn unsigned_abs(self) -> Self::Unsigned;
+
+    // Not public API.
+    #[doc(hidden)]
+    const BITS: u32;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn write_end(self, buf: &mut [BufferByte]) -> &str;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn write_pow2(self, buf: &mut [BufferByte], shift: u32) -> &str;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn write_radix(self, buf: &mut [BufferByte], radix: u32) -> &str;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn written_len(self) -> usize;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn write_forward(self, dst: &mut [u8]) -> Option<usize>;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn write_constant_time(self, buf: &mut [BufferByte]) -> &str;
+
+    // Not public API.
+    #[doc(hidden)]
+    fn write_uninit(self, buf: &mut [MaybeUninit<u8>]) -> &[u8];
+
+    // Not public API.
+    #[doc(hidden)]
+    type Storage: Copy;
+
+    // Not public API.
+    #[doc(hidden)]
+    const STORAGE_INIT: Self::Storage;
+
     // Not public API.
     #[doc(hidden)]
-    fn write(self, buf: &mut Buffer) -> &str;
+    fn write_typed(self, buf: &mut Self::Storage) -> &str;
 }
 
-trait IntegerPrivate<B> {
+trait IntegerPrivate<B: ?Sized> {
     fn write_to(self, buf: &mut B) -> &[u8];
 }
 
-const DEC_DIGITS_LUT: &'static [u8] = b"\
+// Divide by 10^19 one bit at a time, always running all 128 steps and choosing
+// each subtraction by mask, so that the time taken does not depend on `n`.
+#[inline]
+fn divmod_1e19_constant_time(n: u128) -> (u128, u64) {
+    let d = 10_000_000_000_000_000_000_u128;
+    let mut q = 0;
+    let mut r = 0;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        r = (r << 1) | ((n >> i) & 1);
+        // all ones if r >= d, since r < 2 * d
+        let s = (d.wrapping_sub(r).wrapping_sub(1) as i128 >> 127) as u128;
+        r -= d & s;
+        q |= (s & 1) << i;
+    }
+    (q, r as u64)
+}
+
+const RADIX_DIGITS_LUT: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+// Build the table of every two-digit string in `$radix` at compile time, as an
+// array of bytes with the pair for `n` at index `2 * n`. `$glyphs` is indexed
+// by digit value, which also chooses the case of any letters. For example the
+// hexadecimal table with uppercase letters would be
+//
+//     digits_lut!(b"0123456789ABCDEF", 16)
+#[cfg(not(no_const_loop))]
+macro_rules! digits_lut {
+    ($glyphs:expr, $radix:expr) => {{
+        const RADIX: usize = $radix;
+        const fn build() -> [u8; 2 * RADIX * RADIX] {
+            let mut lut = [0; 2 * RADIX * RADIX];
+            let mut n = 0;
+            while n < RADIX * RADIX {
+                lut[2 * n] = $glyphs[n / RADIX];
+                lut[2 * n + 1] = $glyphs[n % RADIX];
+                n += 1;
+            }
+            lut
+        }
+        build()
+    }};
+}
+
+#[cfg(not(no_const_loop))]
+const DEC_DIGITS_LUT: &[u8] = &digits_lut!(RADIX_DIGITS_LUT, 10);
+#[cfg(not(no_const_loop))]
+const HEX_DIGITS_LUT: &[u8] = &digits_lut!(RADIX_DIGITS_LUT, 16);
+
+// Compilers before 1.46 have no loops in const fn, so they get the same tables
+// written out. The tests check these against digits_lut.
+#[cfg(no_const_loop)]
+const DEC_DIGITS_LUT: &[u8] = DEC_DIGITS_LUT_WRITTEN;
+#[cfg(no_const_loop)]
+const HEX_DIGITS_LUT: &[u8] = HEX_DIGITS_LUT_WRITTEN;
+
+#[cfg(any(no_const_loop, test))]
+const DEC_DIGITS_LUT_WRITTEN: &[u8] = b"\
       0001020304050607080910111213141516171819\
       2021222324252627282930313233343536373839\
       4041424344454647484950515253545556575859\
       6061626364656667686970717273747576777879\
       8081828384858687888990919293949596979899";
+#[cfg(any(no_const_loop, test))]
+const HEX_DIGITS_LUT_WRITTEN: &[u8] = b"\
+      000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+      202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f\
+      404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f\
+      606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f\
+      808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f\
+      a0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebf\
+      c0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedf\
+      e0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff";
 
 // Adaptation of the original implementation at
 // https://github.com/rust-lang/rust/blob/b8214dc6c6fc20d0a660fb5700dca9ebf51ebe89/src/libcore/fmt/num.rs#L188-L266
 macro_rules! impl_IntegerCommon {
-    ($max_len:expr, $t:ident) => {
+    ($max_len:expr, $t:ident, $unsigned:ident, $conv_fn:ident, $min_str:expr, $max_str:expr) => {
         impl Integer for $t {
+            const MAX_STR_LEN: usize = $max_len;
+            const SIGNED: bool = <$t>::min_value() != 0;
+            const MIN_STR: &'static str = $min_str;
+            const MAX_STR: &'static str = $max_str;
+            const BITS: u32 = mem::size_of::<$t>() as u32 * 8;
+
+            type Unsigned = $unsigned;
+
+            #[allow(unused_comparisons)]
+            #[inline]
+            fn unsigned_abs(self) -> $unsigned {
+                if self >= 0 {
+                    self as $unsigned
+                } else {
+                    // convert the negative num to positive by summing 1 to it's 2 complement
+                    (!(self as $unsigned)).wrapping_add(1)
+                }
+            }
+
+            type Storage = [BufferByte; $max_len];
+            const STORAGE_INIT: Self::Storage = [BUFFER_BYTE_INIT; $max_len];
+
+            #[cfg(not(feature = "safe"))]
+            #[inline]
+            fn write_typed(self, buf: &mut Self::Storage) -> &str {
+                let bytes = self.write_to(buf);
+                unsafe { str::from_utf8_unchecked(bytes) }
+            }
+
+            #[cfg(feature = "safe")]
+            #[inline]
+            fn write_typed(self, buf: &mut Self::Storage) -> &str {
+                let bytes = self.write_to(&mut buf[..]);
+                str::from_utf8(bytes).unwrap()
+            }
+
+            #[cfg(not(feature = "safe"))]
             #[inline]
-            fn write(self, buf: &mut Buffer) -> &str {
+            fn write_end(self, buf: &mut [BufferByte]) -> &str {
+                assert!(buf.len() >= $max_len);
                 unsafe {
-                    debug_assert!($max_len <= I128_MAX_LEN);
-                    let buf = mem::transmute::<&mut [u8; I128_MAX_LEN], &mut [u8; $max_len]>(
-                        &mut buf.bytes,
-                    );
+                    // print at the end of the buffer to leave room for padding
+                    let offset = buf.len() - $max_len;
+                    let buf = buf.as_mut_ptr().add(offset) as *mut [MaybeUninit<u8>; $max_len];
+                    let buf = &mut *buf;
                     let bytes = self.write_to(buf);
                     str::from_utf8_unchecked(bytes)
                 }
             }
+
+            #[cfg(feature = "safe")]
+            #[inline]
+            fn write_end(self, buf: &mut [BufferByte]) -> &str {
+                // print at the end of the buffer to leave room for padding
+                let offset = buf.len() - $max_len;
+                let bytes = self.write_to(&mut buf[offset..]);
+                str::from_utf8(bytes).unwrap()
+            }
+
+            #[cfg(not(feature = "safe"))]
+            #[inline]
+            fn write_uninit(self, buf: &mut [MaybeUninit<u8>]) -> &[u8] {
+                assert!(buf.len() >= $max_len);
+                unsafe {
+                    let offset = buf.len() - $max_len;
+                    let buf = buf.as_mut_ptr().add(offset) as *mut [MaybeUninit<u8>; $max_len];
+                    self.write_to(&mut *buf)
+                }
+            }
+
+            #[cfg(feature = "safe")]
+            #[inline]
+            fn write_uninit(self, buf: &mut [MaybeUninit<u8>]) -> &[u8] {
+                assert!(buf.len() >= $max_len);
+                let mut bytes = [0u8; $max_len];
+                let len = self.write_to(&mut bytes[..]).len();
+                let offset = buf.len() - len;
+                init_uninit(&mut buf[offset..], &bytes[$max_len - len..])
+            }
+
+            #[inline]
+            fn write_pow2(self, buf: &mut [BufferByte], shift: u32) -> &str {
+                // mask off the sign extension to print the bit pattern of $t
+                let bits = mem::size_of::<$t>() as u32 * 8;
+                let conv_bits = mem::size_of::<$conv_fn>() as u32 * 8;
+                let mut n = (self as $conv_fn) & (<$conv_fn>::max_value() >> (conv_bits - bits));
+                let mask = (1 << shift) - 1;
+                let mut curr = buf.len();
+
+                if shift == 4 {
+                    // two hex digits at a time
+                    while n >= 0x100 {
+                        let d = (n & 0xff) as usize * 2;
+                        n >>= 8;
+                        curr -= 2;
+                        copy_from(&mut buf[curr..], &HEX_DIGITS_LUT[d..d + 2]);
+                    }
+                }
+
+                loop {
+                    curr -= 1;
+                    set_byte(buf, curr, RADIX_DIGITS_LUT[(n & mask) as usize]);
+                    n >>= shift;
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                buffer_ascii(&buf[curr..])
+            }
+
+            #[allow(unused_comparisons)]
+            #[inline]
+            fn written_len(self) -> usize {
+                let is_nonnegative = self >= 0;
+                let n = if is_nonnegative {
+                    self as $conv_fn
+                } else {
+                    // convert the negative num to positive by summing 1 to it's 2 complement
+                    (!(self as $conv_fn)).wrapping_add(1)
+                };
+                let mut len = if is_nonnegative { 1 } else { 2 };
+                let mut pow: $conv_fn = 10;
+                while n >= pow {
+                    len += 1;
+                    pow = match pow.checked_mul(10) {
+                        Some(pow) => pow,
+                        None => break,
+                    };
+                }
+                len
+            }
+
+            #[allow(unused_comparisons)]
+            #[inline]
+            fn write_forward(self, dst: &mut [u8]) -> Option<usize> {
+                let len = self.written_len();
+                if len > dst.len() {
+                    return None;
+                }
+                let is_nonnegative = self >= 0;
+                let mut n = if is_nonnegative {
+                    self as $conv_fn
+                } else {
+                    // convert the negative num to positive by summing 1 to it's 2 complement
+                    (!(self as $conv_fn)).wrapping_add(1)
+                };
+                if !is_nonnegative {
+                    dst[0] = b'-';
+                }
+
+                // the length is known, so the digits land in place from the
+                // last one backwards
+                let mut curr = len;
+                while n >= 100 {
+                    let d = (n % 100) as usize * 2;
+                    n /= 100;
+                    curr -= 2;
+                    dst[curr..curr + 2].copy_from_slice(&DEC_DIGITS_LUT[d..d + 2]);
+                }
+                if n >= 10 {
+                    let d = n as usize * 2;
+                    curr -= 2;
+                    dst[curr..curr + 2].copy_from_slice(&DEC_DIGITS_LUT[d..d + 2]);
+                } else {
+                    curr -= 1;
+                    dst[curr] = n as u8 + b'0';
+                }
+                debug_assert_eq!(curr, if is_nonnegative { 0 } else { 1 });
+                Some(len)
+            }
+
+            #[allow(unused_comparisons)]
+            #[inline]
+            fn write_constant_time(self, buf: &mut [BufferByte]) -> &str {
+                // every digit of the largest magnitude of $t, without the sign
+                let digits = if <$t>::min_value() == 0 {
+                    $max_len
+                } else {
+                    $max_len - 1
+                };
+                let is_negative = (self < 0) as $conv_fn;
+                // two's complement negation, selected by mask instead of a branch
+                let mask = (0 as $conv_fn).wrapping_sub(is_negative);
+                let mut n = ((self as $conv_fn) ^ mask).wrapping_sub(mask) as u128;
+                // room for the digits and the sign
+                assert!(buf.len() > digits);
+                let mut curr = buf.len();
+
+                // 128-bit division by 10 is a library call whose run time
+                // depends on the value, so a 128-bit magnitude is split into
+                // chunks of 19 digits and only u64 is divided by 10. Whether
+                // to split depends on $t alone.
+                let mut remaining = digits;
+                while remaining > 0 {
+                    let (mut chunk, count) = if digits > U64_MAX_LEN && remaining > 19 {
+                        let (q, r) = divmod_1e19_constant_time(n);
+                        n = q;
+                        (r, 19)
+                    } else {
+                        (n as u64, remaining)
+                    };
+                    for _ in 0..count {
+                        curr -= 1;
+                        set_byte(buf, curr, b'0' + (chunk % 10) as u8);
+                        chunk /= 10;
+                    }
+                    remaining -= count;
+                }
+
+                // the sign is always written; only the length depends on it
+                set_byte(buf, curr - 1, b'-');
+                curr -= is_negative as usize;
+
+                buffer_ascii(&buf[curr..])
+            }
+
+            #[allow(unused_comparisons)]
+            #[inline]
+            fn write_radix(self, buf: &mut [BufferByte], radix: u32) -> &str {
+                if radix == 10 {
+                    return self.write_end(buf);
+                }
+
+                let is_nonnegative = self >= 0;
+                let mut n = if is_nonnegative {
+                    self as $conv_fn
+                } else {
+                    // convert the negative num to positive by summing 1 to it's 2 complement
+                    (!(self as $conv_fn)).wrapping_add(1)
+                };
+                let radix = radix as $conv_fn;
+                let mut curr = buf.len();
+
+                if radix == 16 {
+                    // two hex digits at a time
+                    while n >= 0x100 {
+                        let d = (n & 0xff) as usize * 2;
+                        n >>= 8;
+                        curr -= 2;
+                        copy_from(&mut buf[curr..], &HEX_DIGITS_LUT[d..d + 2]);
+                    }
+                }
+
+                loop {
+                    curr -= 1;
+                    set_byte(buf, curr, RADIX_DIGITS_LUT[(n % radix) as usize]);
+                    n /= radix;
+                    if n == 0 {
+                        break;
+                    }
+                }
+
+                if !is_nonnegative {
+                    curr -= 1;
+                    set_byte(buf, curr, b'-');
+                }
+
+                buffer_ascii(&buf[curr..])
+            }
         }
 
         impl private::Sealed for $t {}
@@ -191,13 +3263,14 @@ macro_rules! impl_IntegerCommon {
 }
 
 macro_rules! impl_Integer {
-    ($($max_len:expr => $t:ident),* as $conv_fn:ident) => {$(
-        impl_IntegerCommon!($max_len, $t);
+    ($($max_len:expr => $t:ident: $unsigned:ident($min_str:expr, $max_str:expr)),* as $conv_fn:ident) => {$(
+        impl_IntegerCommon!($max_len, $t, $unsigned, $conv_fn, $min_str, $max_str);
 
-        impl IntegerPrivate<[u8; $max_len]> for $t {
+        #[cfg(not(feature = "safe"))]
+        impl IntegerPrivate<[MaybeUninit<u8>; $max_len]> for $t {
             #[allow(unused_comparisons)]
             #[inline]
-            fn write_to(self, buf: &mut [u8; $max_len]) -> &[u8] {
+            fn write_to(self, buf: &mut [MaybeUninit<u8>; $max_len]) -> &[u8] {
                 let is_nonnegative = self >= 0;
                 let mut n = if is_nonnegative {
                     self as $conv_fn
@@ -206,10 +3279,34 @@ macro_rules! impl_Integer {
                     (!(self as $conv_fn)).wrapping_add(1)
                 };
                 let mut curr = buf.len() as isize;
-                let buf_ptr = buf.as_mut_ptr();
+                let buf_ptr = buf.as_mut_ptr() as *mut u8;
                 let lut_ptr = DEC_DIGITS_LUT.as_ptr();
 
                 unsafe {
+                    // peel off the low 8 or 16 digits of large 64-bit numbers in parallel
+                    #[cfg(all(
+                        feature = "simd",
+                        any(
+                            target_arch = "x86",
+                            target_arch = "x86_64",
+                            all(target_arch = "aarch64", target_feature = "neon", not(no_aarch64_neon)),
+                        )
+                    ))]
+                    {
+                        if mem::size_of::<$conv_fn>() == 8 && simd::available() {
+                            let m = n as u64;
+                            if m >= 10_000_000_000_000_000 {
+                                curr -= 16;
+                                simd::write_16_digits(m % 10_000_000_000_000_000, buf_ptr.offset(curr));
+                                n = (m / 10_000_000_000_000_000) as $conv_fn;
+                            } else if m >= 100_000_000 {
+                                curr -= 8;
+                                simd::write_8_digits((m % 100_000_000) as u32, buf_ptr.offset(curr));
+                                n = (m / 100_000_000) as $conv_fn;
+                            }
+                        }
+                    }
+
                     // need at least 16 bits for the 4-characters-at-a-time to work.
                     if mem::size_of::<$t>() >= 2 {
                         // eagerly decode 4 characters at a time
@@ -252,10 +3349,73 @@ macro_rules! impl_Integer {
                     }
                 }
 
+                debug_assert!(curr >= 0);
                 let len = buf.len() - curr as usize;
+                debug_assert_eq!(len, self.written_len());
                 unsafe { slice::from_raw_parts(buf_ptr.offset(curr), len) }
             }
         }
+
+        #[cfg(feature = "safe")]
+        impl IntegerPrivate<[u8]> for $t {
+            #[allow(unused_comparisons)]
+            #[inline]
+            fn write_to(self, buf: &mut [u8]) -> &[u8] {
+                let is_nonnegative = self >= 0;
+                let mut n = if is_nonnegative {
+                    self as $conv_fn
+                } else {
+                    // convert the negative num to positive by summing 1 to it's 2 complement
+                    (!(self as $conv_fn)).wrapping_add(1)
+                };
+                let mut curr = buf.len();
+                let lut = DEC_DIGITS_LUT;
+
+                // need at least 16 bits for the 4-characters-at-a-time to work.
+                if mem::size_of::<$t>() >= 2 {
+                    // eagerly decode 4 characters at a time
+                    while n >= 10000 {
+                        let rem = (n % 10000) as usize;
+                        n /= 10000;
+
+                        let d1 = (rem / 100) << 1;
+                        let d2 = (rem % 100) << 1;
+                        curr -= 4;
+                        buf[curr..curr + 2].copy_from_slice(&lut[d1..d1 + 2]);
+                        buf[curr + 2..curr + 4].copy_from_slice(&lut[d2..d2 + 2]);
+                    }
+                }
+
+                // if we reach here numbers are <= 9999, so at most 4 chars long
+                let mut n = n as usize; // possibly reduce 64bit math
+
+                // decode 2 more chars, if > 2 chars
+                if n >= 100 {
+                    let d1 = (n % 100) << 1;
+                    n /= 100;
+                    curr -= 2;
+                    buf[curr..curr + 2].copy_from_slice(&lut[d1..d1 + 2]);
+                }
+
+                // decode last 1 or 2 chars
+                if n < 10 {
+                    curr -= 1;
+                    buf[curr] = (n as u8) + b'0';
+                } else {
+                    let d1 = n << 1;
+                    curr -= 2;
+                    buf[curr..curr + 2].copy_from_slice(&lut[d1..d1 + 2]);
+                }
+
+                if !is_nonnegative {
+                    curr -= 1;
+                    buf[curr] = b'-';
+                }
+
+                debug_assert_eq!(buf.len() - curr, self.written_len());
+                &buf[curr..]
+            }
+        }
     )*};
 }
 
@@ -269,34 +3429,47 @@ const I64_MAX_LEN: usize = 20;
 const U64_MAX_LEN: usize = 20;
 
 impl_Integer!(
-    I8_MAX_LEN => i8,
-    U8_MAX_LEN => u8,
-    I16_MAX_LEN => i16,
-    U16_MAX_LEN => u16,
-    I32_MAX_LEN => i32,
-    U32_MAX_LEN => u32
+    I8_MAX_LEN => i8: u8("-128", "127"),
+    U8_MAX_LEN => u8: u8("0", "255"),
+    I16_MAX_LEN => i16: u16("-32768", "32767"),
+    U16_MAX_LEN => u16: u16("0", "65535"),
+    I32_MAX_LEN => i32: u32("-2147483648", "2147483647"),
+    U32_MAX_LEN => u32: u32("0", "4294967295")
     as u32);
 
-impl_Integer!(I64_MAX_LEN => i64, U64_MAX_LEN => u64 as u64);
+impl_Integer!(
+    I64_MAX_LEN => i64: u64("-9223372036854775808", "9223372036854775807"),
+    U64_MAX_LEN => u64: u64("0", "18446744073709551615")
+    as u64);
 
 #[cfg(target_pointer_width = "16")]
-impl_Integer!(I16_MAX_LEN => isize, U16_MAX_LEN => usize as u16);
+impl_Integer!(
+    I16_MAX_LEN => isize: usize("-32768", "32767"),
+    U16_MAX_LEN => usize: usize("0", "65535")
+    as u16);
 
 #[cfg(target_pointer_width = "32")]
-impl_Integer!(I32_MAX_LEN => isize, U32_MAX_LEN => usize as u32);
+impl_Integer!(
+    I32_MAX_LEN => isize: usize("-2147483648", "2147483647"),
+    U32_MAX_LEN => usize: usize("0", "4294967295")
+    as u32);
 
 #[cfg(target_pointer_width = "64")]
-impl_Integer!(I64_MAX_LEN => isize, U64_MAX_LEN => usize as u64);
+impl_Integer!(
+    I64_MAX_LEN => isize: usize("-9223372036854775808", "9223372036854775807"),
+    U64_MAX_LEN => usize: usize("0", "18446744073709551615")
+    as u64);
 
-#[cfg(all(feature = "i128"))]
+#[cfg(feature = "i128")]
 macro_rules! impl_Integer128 {
-    ($($max_len:expr => $t:ident),*) => {$(
-        impl_IntegerCommon!($max_len, $t);
+    ($($max_len:expr => $t:ident: $unsigned:ident($min_str:expr, $max_str:expr)),*) => {$(
+        impl_IntegerCommon!($max_len, $t, $unsigned, u128, $min_str, $max_str);
 
-        impl IntegerPrivate<[u8; $max_len]> for $t {
+        #[cfg(not(feature = "safe"))]
+        impl IntegerPrivate<[MaybeUninit<u8>; $max_len]> for $t {
             #[allow(unused_comparisons)]
             #[inline]
-            fn write_to(self, buf: &mut [u8; $max_len]) -> &[u8] {
+            fn write_to(self, buf: &mut [MaybeUninit<u8>; $max_len]) -> &[u8] {
                 let is_nonnegative = self >= 0;
                 let n = if is_nonnegative {
                     self as u128
@@ -305,15 +3478,20 @@ macro_rules! impl_Integer128 {
                     (!(self as u128)).wrapping_add(1)
                 };
                 let mut curr = buf.len() as isize;
-                let buf_ptr = buf.as_mut_ptr();
+                let buf_ptr = buf.as_mut_ptr() as *mut u8;
 
                 unsafe {
-                    // Divide by 10^19 which is the highest power less than 2^64.
-                    let (n, rem) = udiv128::udivmod_1e19(n);
-                    let buf1 = buf_ptr.offset(curr - U64_MAX_LEN as isize) as *mut [u8; U64_MAX_LEN];
-                    curr -= rem.write_to(&mut *buf1).len() as isize;
+                    let buf1 = buf_ptr.offset(curr - U64_MAX_LEN as isize) as *mut [MaybeUninit<u8>; U64_MAX_LEN];
+
+                    if n <= <u64>::max_value() as u128 {
+                        // Small values go straight through the u64 path.
+                        curr -= (n as u64).write_to(&mut *buf1).len() as isize;
+                    } else {
+                        // Divide by 10^19 which is the highest power less than 2^64.
+                        // The quotient is nonzero because n is at least 2^64.
+                        let (n, rem) = udiv128::udivmod_1e19(n);
+                        curr -= rem.write_to(&mut *buf1).len() as isize;
 
-                    if n != 0 {
                         // Memset the base10 leading zeros of rem.
                         let target = buf.len() as isize - 19;
                         ptr::write_bytes(buf_ptr.offset(target), b'0', (curr - target) as usize);
@@ -321,7 +3499,7 @@ macro_rules! impl_Integer128 {
 
                         // Divide by 10^19 again.
                         let (n, rem) = udiv128::udivmod_1e19(n);
-                        let buf2 = buf_ptr.offset(curr - U64_MAX_LEN as isize) as *mut [u8; U64_MAX_LEN];
+                        let buf2 = buf_ptr.offset(curr - U64_MAX_LEN as isize) as *mut [MaybeUninit<u8>; U64_MAX_LEN];
                         curr -= rem.write_to(&mut *buf2).len() as isize;
 
                         if n != 0 {
@@ -342,17 +3520,316 @@ macro_rules! impl_Integer128 {
                         *buf_ptr.offset(curr) = b'-';
                     }
 
+                    debug_assert!(curr >= 0);
                     let len = buf.len() - curr as usize;
+                    debug_assert_eq!(len, self.written_len());
                     slice::from_raw_parts(buf_ptr.offset(curr), len)
                 }
             }
         }
+
+        #[cfg(feature = "safe")]
+        impl IntegerPrivate<[u8]> for $t {
+            #[allow(unused_comparisons)]
+            #[inline]
+            fn write_to(self, buf: &mut [u8]) -> &[u8] {
+                let is_nonnegative = self >= 0;
+                let n = if is_nonnegative {
+                    self as u128
+                } else {
+                    // convert the negative num to positive by summing 1 to it's 2 complement
+                    (!(self as u128)).wrapping_add(1)
+                };
+                let len = buf.len();
+                let mut curr = len;
+
+                if n <= <u64>::max_value() as u128 {
+                    // Small values go straight through the u64 path.
+                    curr -= (n as u64).write_to(&mut buf[..curr]).len();
+                } else {
+                    // Divide by 10^19 which is the highest power less than 2^64.
+                    // The quotient is nonzero because n is at least 2^64.
+                    let (n, rem) = udiv128::udivmod_1e19(n);
+                    curr -= rem.write_to(&mut buf[..curr]).len();
+
+                    // Fill in the base10 leading zeros of rem.
+                    let target = len - 19;
+                    for b in &mut buf[target..curr] {
+                        *b = b'0';
+                    }
+                    curr = target;
+
+                    // Divide by 10^19 again.
+                    let (n, rem) = udiv128::udivmod_1e19(n);
+                    curr -= rem.write_to(&mut buf[..curr]).len();
+
+                    if n != 0 {
+                        // Fill in the leading zeros.
+                        let target = len - 38;
+                        for b in &mut buf[target..curr] {
+                            *b = b'0';
+                        }
+                        curr = target;
+
+                        // There is at most one digit left
+                        // because u128::max / 10^19 / 10^19 is 3.
+                        curr -= 1;
+                        buf[curr] = (n as u8) + b'0';
+                    }
+                }
+
+                if !is_nonnegative {
+                    curr -= 1;
+                    buf[curr] = b'-';
+                }
+
+                debug_assert_eq!(buf.len() - curr, self.written_len());
+                &buf[curr..]
+            }
+        }
     )*};
 }
 
-#[cfg(all(feature = "i128"))]
+#[cfg(feature = "i128")]
 const U128_MAX_LEN: usize = 39;
-const I128_MAX_LEN: usize = 40;
+#[cfg(feature = "i128")]
+const I128_MAX_LEN: usize = MAX_LEN;
 
-#[cfg(all(feature = "i128"))]
-impl_Integer128!(I128_MAX_LEN => i128, U128_MAX_LEN => u128);
+// u128::max_value() in base 2.
+const BIN_MAX_LEN: usize = 128;
+// i128::min_value() in base 10 with a separator between every digit.
+const GROUPED_MAX_LEN: usize = 78;
+// u128::max_value() in base 2 after a 0b prefix, which is one byte longer than
+// i128::min_value() in base 2 with a `-`.
+const PREFIXED_MAX_LEN: usize = BIN_MAX_LEN + 2;
+
+const BUFFER_LEN: usize = MAX_LEN;
+const WIDE_BUFFER_LEN: usize = PREFIXED_MAX_LEN;
+
+// Length of the longest base 10 output of an integer that is `bytes` wide,
+// including the sign if it is signed. The magnitude has at most `bits`
+// significant bits, so it has floor(bits * log10(2)) + 1 digits, with log10(2)
+// approximated closely enough for the result to be exact up to 128 bits.
+const fn dec_max_len(bytes: usize, signed: bool) -> usize {
+    let bits = bytes * 8 - signed as usize;
+    bits * 30_103 / 100_000 + 1 + signed as usize
+}
+
+// Fails to compile, with an array of 0 elements where 1 is expected, unless
+// every condition holds.
+macro_rules! const_assert {
+    ($name:ident: $($cond:expr),* $(,)*) => {
+        #[allow(dead_code)]
+        const $name: [(); 1] = [(); ($($cond &&)* true) as usize];
+    };
+}
+
+// Whether the MAX_STR_LEN that `$t` was given matches its size and signedness.
+macro_rules! max_str_len_ok {
+    ($t:ty) => {
+        <$t as Integer>::MAX_STR_LEN == dec_max_len(mem::size_of::<$t>(), <$t as Integer>::SIGNED)
+    };
+}
+
+const_assert!(MAX_STR_LEN_CHECK:
+    max_str_len_ok!(i8),
+    max_str_len_ok!(u8),
+    max_str_len_ok!(i16),
+    max_str_len_ok!(u16),
+    max_str_len_ok!(i32),
+    max_str_len_ok!(u32),
+    max_str_len_ok!(i64),
+    max_str_len_ok!(u64),
+    max_str_len_ok!(isize),
+    max_str_len_ok!(usize),
+);
+
+#[cfg(feature = "i128")]
+const_assert!(MAX_STR_LEN_CHECK_128:
+    max_str_len_ok!(i128),
+    max_str_len_ok!(u128),
+    <i128 as Integer>::MAX_STR_LEN == BUFFER_LEN,
+);
+
+const_assert!(BUFFER_LEN_CHECK:
+    MAX_LEN == dec_max_len(mem::size_of::<u128>(), true),
+    BIN_MAX_LEN == mem::size_of::<u128>() * 8,
+    // A sign, 39 digits and 38 separators.
+    GROUPED_MAX_LEN == 2 * MAX_LEN - 2,
+    PREFIXED_MAX_LEN == 2 + mem::size_of::<u128>() * 8,
+    BUFFER_LEN == MAX_LEN,
+    WIDE_BUFFER_LEN >= GROUPED_MAX_LEN,
+    WIDE_BUFFER_LEN >= PREFIXED_MAX_LEN,
+    FORMATTED_MAX_WIDTH >= MAX_LEN,
+);
+
+#[cfg(feature = "i128")]
+impl_Integer128!(
+    I128_MAX_LEN => i128: u128(
+        "-170141183460469231731687303715884105728",
+        "170141183460469231731687303715884105727"
+    ),
+    U128_MAX_LEN => u128: u128("0", "340282366920938463463374607431768211455")
+);
+
+// isize and usize share the implementation of the primitive with the same
+// width. 16, 32 and 64 bit pointers are handled above; 128 bit pointers need
+// the i128 feature, and any other width is rejected up front rather than
+// leaving isize and usize without an Integer impl.
+#[cfg(all(feature = "i128", target_pointer_width = "128"))]
+impl_Integer128!(
+    I128_MAX_LEN => isize: usize(
+        "-170141183460469231731687303715884105728",
+        "170141183460469231731687303715884105727"
+    ),
+    U128_MAX_LEN => usize: usize("0", "340282366920938463463374607431768211455")
+);
+
+#[cfg(all(not(feature = "i128"), target_pointer_width = "128"))]
+compile_error!("itoa requires the `i128` feature on targets with 128-bit pointers");
+
+#[cfg(not(any(
+    target_pointer_width = "16",
+    target_pointer_width = "32",
+    target_pointer_width = "64",
+    target_pointer_width = "128"
+)))]
+compile_error!("itoa does not support the pointer width of this target");
+
+// Forward every method of Integer to the integer that `$inner` extracts from
+// `$n`, which is bound to self. The smallest value is that of `$inner_ty`
+// unless `$min_str` says otherwise.
+macro_rules! delegate_Integer {
+    ($inner_ty:ty, |$n:ident| $inner:expr) => {
+        delegate_Integer!($inner_ty, <$inner_ty as Integer>::MIN_STR, |$n| $inner);
+    };
+    ($inner_ty:ty, $min_str:expr, |$n:ident| $inner:expr) => {
+        const MAX_STR_LEN: usize = <$inner_ty as Integer>::MAX_STR_LEN;
+        const SIGNED: bool = <$inner_ty as Integer>::SIGNED;
+        const MIN_STR: &'static str = $min_str;
+        const MAX_STR: &'static str = <$inner_ty as Integer>::MAX_STR;
+        const BITS: u32 = <$inner_ty as Integer>::BITS;
+
+        type Unsigned = <$inner_ty as Integer>::Unsigned;
+
+        #[inline]
+        fn unsigned_abs(self) -> Self::Unsigned {
+            let $n = self;
+            Integer::unsigned_abs($inner)
+        }
+
+        type Storage = <$inner_ty as Integer>::Storage;
+        const STORAGE_INIT: Self::Storage = <$inner_ty as Integer>::STORAGE_INIT;
+
+        #[inline]
+        fn write_typed(self, buf: &mut Self::Storage) -> &str {
+            let $n = self;
+            $inner.write_typed(buf)
+        }
+
+        #[inline]
+        fn write_end(self, buf: &mut [BufferByte]) -> &str {
+            let $n = self;
+            $inner.write_end(buf)
+        }
+
+        #[inline]
+        fn write_pow2(self, buf: &mut [BufferByte], shift: u32) -> &str {
+            let $n = self;
+            $inner.write_pow2(buf, shift)
+        }
+
+        #[inline]
+        fn write_radix(self, buf: &mut [BufferByte], radix: u32) -> &str {
+            let $n = self;
+            $inner.write_radix(buf, radix)
+        }
+
+        #[inline]
+        fn written_len(self) -> usize {
+            let $n = self;
+            $inner.written_len()
+        }
+
+        #[inline]
+        fn write_forward(self, dst: &mut [u8]) -> Option<usize> {
+            let $n = self;
+            $inner.write_forward(dst)
+        }
+
+        #[inline]
+        fn write_constant_time(self, buf: &mut [BufferByte]) -> &str {
+            let $n = self;
+            $inner.write_constant_time(buf)
+        }
+
+        #[inline]
+        fn write_uninit(self, buf: &mut [MaybeUninit<u8>]) -> &[u8] {
+            let $n = self;
+            $inner.write_uninit(buf)
+        }
+    };
+}
+
+macro_rules! impl_NonZero {
+    ($($t:ident => $inner:ident($min_str:expr)),*) => {$(
+        impl Integer for num::$t {
+            delegate_Integer!($inner, $min_str, |n| n.get());
+        }
+
+        impl private::Sealed for num::$t {}
+    )*};
+}
+
+impl_NonZero!(
+    NonZeroI8 => i8(i8::MIN_STR),
+    NonZeroU8 => u8("1"),
+    NonZeroI16 => i16(i16::MIN_STR),
+    NonZeroU16 => u16("1"),
+    NonZeroI32 => i32(i32::MIN_STR),
+    NonZeroU32 => u32("1"),
+    NonZeroI64 => i64(i64::MIN_STR),
+    NonZeroU64 => u64("1"),
+    NonZeroIsize => isize(isize::MIN_STR),
+    NonZeroUsize => usize("1")
+);
+
+#[cfg(feature = "i128")]
+impl_NonZero!(NonZeroI128 => i128(i128::MIN_STR), NonZeroU128 => u128("1"));
+
+impl<T: Integer> Integer for num::Wrapping<T> {
+    delegate_Integer!(T, |n| n.0);
+}
+
+impl<T: Integer> private::Sealed for num::Wrapping<T> {}
+
+#[cfg(not(no_core_num_saturating))]
+#[allow(clippy::incompatible_msrv)] // gated on compiler version by build.rs
+impl<T: Integer> Integer for num::Saturating<T> {
+    delegate_Integer!(T, |n| n.0);
+}
+
+#[cfg(not(no_core_num_saturating))]
+#[allow(clippy::incompatible_msrv)] // gated on compiler version by build.rs
+impl<T: Integer> private::Sealed for num::Saturating<T> {}
+
+impl<T: Integer + Copy> Integer for &T {
+    delegate_Integer!(T, |n| *n);
+}
+
+impl<T: Integer + Copy> private::Sealed for &T {}
+
+// Declared after delegate_Integer so that the macro is in scope.
+#[cfg(not(no_target_has_atomic))]
+mod atomic;
+
+#[cfg(all(test, not(no_const_loop)))]
+mod tests {
+    use super::{DEC_DIGITS_LUT, DEC_DIGITS_LUT_WRITTEN, HEX_DIGITS_LUT, HEX_DIGITS_LUT_WRITTEN};
+
+    #[test]
+    fn digits_lut_matches_written_tables() {
+        assert_eq!(DEC_DIGITS_LUT, DEC_DIGITS_LUT_WRITTEN);
+        assert_eq!(HEX_DIGITS_LUT, HEX_DIGITS_LUT_WRITTEN);
+    }
+}
@@ -0,0 +1,22 @@
+use Buffer;
+
+/// Format integer right-aligned and padded with spaces into an array of
+/// exactly `N` bytes.
+///
+/// Returns `None` if the integer has more than `N` digits.
+///
+/// ```
+/// assert_eq!(itoa::to_padded_array::<6>(1234), Some(*b"  1234"));
+/// assert_eq!(itoa::to_padded_array::<3>(1234), None);
+/// ```
+#[inline]
+pub fn to_padded_array<const N: usize>(value: u64) -> Option<[u8; N]> {
+    let mut buf = Buffer::new();
+    let digits = buf.format(value).as_bytes();
+    if digits.len() > N {
+        return None;
+    }
+    let mut array = [b' '; N];
+    array[N - digits.len()..].copy_from_slice(digits);
+    Some(array)
+}
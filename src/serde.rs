@@ -0,0 +1,34 @@
+//! Serialize integers as strings.
+//!
+//! ```
+//! # extern crate serde;
+//! # extern crate serde_json;
+//! # use serde::Serialize;
+//! #
+//! #[derive(Serialize)]
+//! struct Event {
+//!     #[serde(serialize_with = "itoa::serde::as_str")]
+//!     id: u64,
+//! }
+//!
+//! let event = Event { id: 9007199254740993 };
+//! let json = serde_json::to_string(&event).unwrap();
+//! assert_eq!(json, r#"{"id":"9007199254740993"}"#);
+//! ```
+
+extern crate serde;
+
+use self::serde::Serializer;
+use {Buffer, Integer};
+
+/// Serialize an integer as its base 10 string, for use with
+/// `#[serde(serialize_with = "itoa::serde::as_str")]`.
+#[inline]
+pub fn as_str<S, I>(value: &I, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    I: Integer + Copy,
+{
+    let mut buf = Buffer::new();
+    serializer.serialize_str(buf.format(*value))
+}
@@ -0,0 +1,269 @@
+// SSE2 and NEON conversion of 8 decimal digits at a time, adapted from Wojciech
+// Muła's algorithm as implemented in
+// https://github.com/miloyip/itoa-benchmark/blob/940542a7770155ee3e9f2777ebc178dc899b43e0/src/sse2.cpp
+//
+// The digits of abcdefgh are computed in 16-bit lanes by dividing by 10^3,
+// 10^2, 10^1 and 10^0 with multiply-high instructions and then subtracting ten
+// times the neighboring lane.
+
+#[cfg(not(feature = "std"))]
+use core::arch;
+#[cfg(feature = "std")]
+use std::arch;
+
+#[cfg(target_arch = "aarch64")]
+use self::arch::aarch64::*;
+#[cfg(target_arch = "x86")]
+use self::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use self::arch::x86_64::*;
+
+// ceil(2^45 / 10^4)
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+const DIV_10000: u32 = 0xd1b7_1759;
+
+// The same multipliers as the SSE2 path passes to _mm_mulhi_epu16.
+#[cfg(target_arch = "aarch64")]
+const DIV_POWERS: [u16; 8] = [8389, 5243, 13108, 0x8000, 8389, 5243, 13108, 0x8000];
+#[cfg(target_arch = "aarch64")]
+const SHIFT_POWERS: [u16; 8] = [
+    1 << 7,
+    1 << 11,
+    1 << 13,
+    0x8000,
+    1 << 7,
+    1 << 11,
+    1 << 13,
+    0x8000,
+];
+
+#[inline]
+pub fn available() -> bool {
+    // NEON is part of the baseline of the aarch64 targets this module is
+    // compiled for.
+    #[cfg(any(target_arch = "aarch64", target_feature = "sse2"))]
+    {
+        true
+    }
+
+    #[cfg(all(
+        not(target_arch = "aarch64"),
+        not(target_feature = "sse2"),
+        feature = "std"
+    ))]
+    {
+        is_x86_feature_detected!("sse2")
+    }
+
+    #[cfg(all(
+        not(target_arch = "aarch64"),
+        not(target_feature = "sse2"),
+        not(feature = "std")
+    ))]
+    {
+        false
+    }
+}
+
+// Write the 8 digits of `value`, including leading zeros, to `dst`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn write_8_digits(value: u32, dst: *mut u8) {
+    let digits = _mm_packus_epi16(convert_8_digits(value), _mm_setzero_si128());
+    let ascii = _mm_add_epi8(digits, _mm_set1_epi8(b'0' as i8));
+    _mm_storel_epi64(dst as *mut __m128i, ascii);
+}
+
+// Write the 16 digits of `value`, including leading zeros, to `dst`.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+#[target_feature(enable = "sse2")]
+pub unsafe fn write_16_digits(value: u64, dst: *mut u8) {
+    debug_assert!(value < 10_000_000_000_000_000);
+    let hi = convert_8_digits((value / 100_000_000) as u32);
+    let lo = convert_8_digits((value % 100_000_000) as u32);
+    let digits = _mm_packus_epi16(hi, lo);
+    let ascii = _mm_add_epi8(digits, _mm_set1_epi8(b'0' as i8));
+    _mm_storeu_si128(dst as *mut __m128i, ascii);
+}
+
+// Returns the digits of `value` in eight 16-bit lanes, most significant first.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn convert_8_digits(value: u32) -> __m128i {
+    debug_assert!(value <= 99_999_999);
+
+    // abcd, efgh = abcdefgh divmod 10000
+    let abcdefgh = _mm_cvtsi32_si128(value as i32);
+    let abcd = _mm_srli_epi64(
+        _mm_mul_epu32(abcdefgh, _mm_set1_epi32(DIV_10000 as i32)),
+        45,
+    );
+    let efgh = _mm_sub_epi32(abcdefgh, _mm_mul_epu32(abcd, _mm_set1_epi32(10000)));
+
+    // v1 = [abcd, efgh, 0, 0, 0, 0, 0, 0]
+    let v1 = _mm_unpacklo_epi16(abcd, efgh);
+
+    // v1a = v1 * 4 = [abcd * 4, efgh * 4, 0, 0, 0, 0, 0, 0]
+    let v1a = _mm_slli_epi64(v1, 2);
+
+    // v2 = [abcd * 4, abcd * 4, abcd * 4, abcd * 4, efgh * 4, efgh * 4, efgh * 4, efgh * 4]
+    let v2a = _mm_unpacklo_epi16(v1a, v1a);
+    let v2 = _mm_unpacklo_epi32(v2a, v2a);
+
+    // v4 = v2 div 10^3, 10^2, 10^1, 10^0 = [a, ab, abc, abcd, e, ef, efg, efgh]
+    let div_powers = _mm_setr_epi16(
+        8389,
+        5243,
+        13108,
+        0x8000u16 as i16,
+        8389,
+        5243,
+        13108,
+        0x8000u16 as i16,
+    );
+    let shift_powers = _mm_setr_epi16(
+        1 << 7,
+        1 << 11,
+        1 << 13,
+        0x8000u16 as i16,
+        1 << 7,
+        1 << 11,
+        1 << 13,
+        0x8000u16 as i16,
+    );
+    let v3 = _mm_mulhi_epu16(v2, div_powers);
+    let v4 = _mm_mulhi_epu16(v3, shift_powers);
+
+    // v5 = v4 * 10 = [a0, ab0, abc0, abcd0, e0, ef0, efg0, efgh0]
+    let v5 = _mm_mullo_epi16(v4, _mm_set1_epi16(10));
+
+    // v6 = v5 << 16 = [0, a0, ab0, abc0, 0, e0, ef0, efg0]
+    let v6 = _mm_slli_epi64(v5, 16);
+
+    // v7 = v4 - v6 = [a, b, c, d, e, f, g, h]
+    _mm_sub_epi16(v4, v6)
+}
+
+// Write the 8 digits of `value`, including leading zeros, to `dst`.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub unsafe fn write_8_digits(value: u32, dst: *mut u8) {
+    let digits = vmovn_u16(convert_8_digits(value));
+    vst1_u8(dst, vadd_u8(digits, vdup_n_u8(b'0')));
+}
+
+// Write the 16 digits of `value`, including leading zeros, to `dst`.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+pub unsafe fn write_16_digits(value: u64, dst: *mut u8) {
+    debug_assert!(value < 10_000_000_000_000_000);
+    let hi = vmovn_u16(convert_8_digits((value / 100_000_000) as u32));
+    let lo = vmovn_u16(convert_8_digits((value % 100_000_000) as u32));
+    vst1q_u8(dst, vaddq_u8(vcombine_u8(hi, lo), vdupq_n_u8(b'0')));
+}
+
+// Returns the digits of `value` in eight 16-bit lanes, most significant first.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+unsafe fn convert_8_digits(value: u32) -> uint16x8_t {
+    debug_assert!(value <= 99_999_999);
+
+    // abcd, efgh = abcdefgh divmod 10000
+    let abcd = value / 10000;
+    let efgh = value % 10000;
+
+    // v2 = [abcd * 4, abcd * 4, abcd * 4, abcd * 4, efgh * 4, efgh * 4, efgh * 4, efgh * 4]
+    let v2 = vcombine_u16(vdup_n_u16((abcd * 4) as u16), vdup_n_u16((efgh * 4) as u16));
+
+    // v4 = v2 div 10^3, 10^2, 10^1, 10^0 = [a, ab, abc, abcd, e, ef, efg, efgh]
+    let v3 = mulhi(v2, vld1q_u16(DIV_POWERS.as_ptr()));
+    let v4 = mulhi(v3, vld1q_u16(SHIFT_POWERS.as_ptr()));
+
+    // v5 = v4 * 10 = [a0, ab0, abc0, abcd0, e0, ef0, efg0, efgh0]
+    let v5 = vmulq_n_u16(v4, 10);
+
+    // v6 = v5 moved up a lane = [0, a0, ab0, abc0, 0, e0, ef0, efg0]
+    let v6 = vsetq_lane_u16::<4>(0, vextq_u16::<7>(vdupq_n_u16(0), v5));
+
+    // v7 = v4 - v6 = [a, b, c, d, e, f, g, h]
+    vsubq_u16(v4, v6)
+}
+
+// The high half of each 16-bit lane of `a * b`, which NEON has no single
+// instruction for.
+#[cfg(target_arch = "aarch64")]
+#[inline]
+unsafe fn mulhi(a: uint16x8_t, b: uint16x8_t) -> uint16x8_t {
+    let lo = vshrn_n_u32::<16>(vmull_u16(vget_low_u16(a), vget_low_u16(b)));
+    vshrn_high_n_u32::<16>(lo, vmull_high_u16(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{available, write_16_digits, write_8_digits};
+
+    // xorshift64*, the same as the one in tests/test.rs.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+    }
+
+    // The digits of `n`, including leading zeros, one at a time from the end.
+    fn scalar_digits(mut n: u64, dst: &mut [u8]) {
+        for b in dst.iter_mut().rev() {
+            *b = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+    }
+
+    fn check_8(n: u32) {
+        let mut simd = [0u8; 8];
+        let mut scalar = [0u8; 8];
+        unsafe { write_8_digits(n, simd.as_mut_ptr()) };
+        scalar_digits(u64::from(n), &mut scalar);
+        assert_eq!(simd, scalar, "{}", n);
+    }
+
+    fn check_16(n: u64) {
+        let mut simd = [0u8; 16];
+        let mut scalar = [0u8; 16];
+        unsafe { write_16_digits(n, simd.as_mut_ptr()) };
+        scalar_digits(n, &mut scalar);
+        assert_eq!(simd, scalar, "{}", n);
+    }
+
+    #[test]
+    fn simd_matches_scalar() {
+        assert!(available());
+
+        for &n in &[0, 1, 9, 10, 9_999, 10_000, 10_001, 99_999_999] {
+            check_8(n);
+        }
+        for &n in &[
+            0,
+            1,
+            99_999_999,
+            100_000_000,
+            100_000_001,
+            9_999_999_999_999_999,
+        ] {
+            check_16(n);
+        }
+
+        let mut rng = Rng(0x94d0_49bb_1331_11eb);
+        for _ in 0..100_000 {
+            let bits = rng.next() >> (rng.next() % 64);
+            check_8((bits % 100_000_000) as u32);
+            check_16(bits % 10_000_000_000_000_000);
+        }
+    }
+}
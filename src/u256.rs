@@ -0,0 +1,36 @@
+use ethnum::{I256, U256};
+use DivMod1e19;
+
+const TEN_POW_19: u128 = 10_000_000_000_000_000_000;
+
+impl DivMod1e19 for U256 {
+    #[inline]
+    fn divmod_1e19(self) -> (Self, u64) {
+        let d = U256::new(TEN_POW_19);
+        (self / d, (self % d).as_u64())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == U256::ZERO
+    }
+}
+
+impl DivMod1e19 for I256 {
+    #[inline]
+    fn divmod_1e19(self) -> (Self, u64) {
+        let d = I256::new(TEN_POW_19 as i128);
+        // Division truncates, so the remainder has the sign of `self`.
+        (self / d, (self % d).unsigned_abs().as_u64())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        *self == I256::ZERO
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        I256::is_negative(*self)
+    }
+}
@@ -1,7 +1,21 @@
-#![cfg_attr(feature = "cargo-clippy", allow(cast_lossless, string_lit_as_bytes))]
+#![allow(clippy::cast_lossless, clippy::string_lit_as_bytes)]
 #![allow(non_snake_case)]
 
+#[cfg(feature = "arrayvec")]
+extern crate arrayvec;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "ethnum")]
+extern crate ethnum;
+#[cfg(feature = "heapless")]
+extern crate heapless;
 extern crate itoa;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
 
 macro_rules! test {
     (
@@ -45,3 +59,2583 @@ test! {
     #[cfg(feature = "i128")]
     test_i128_min(<i128>::min_value(), "-170141183460469231731687303715884105728")
 }
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_u128_narrow() {
+    let mut buffer = itoa::Buffer::new();
+    let max = <u64>::max_value() as u128;
+    assert_eq!(buffer.format_u128_narrow(0), "0");
+    assert_eq!(buffer.format_u128_narrow(max), "18446744073709551615");
+    assert_eq!(buffer.format_u128_narrow(max + 1), "18446744073709551616");
+    assert_eq!(
+        buffer.format_u128_narrow(<u128>::max_value()),
+        "340282366920938463463374607431768211455"
+    );
+    assert_eq!(
+        buffer.last(),
+        Some("340282366920938463463374607431768211455")
+    );
+}
+
+#[test]
+fn test_detailed() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_detailed(42i32), ("42", false));
+    assert_eq!(buffer.format_detailed(-42i32), ("-42", true));
+    assert_eq!(buffer.format_detailed(0i32), ("0", false));
+    assert_eq!(
+        buffer.format_detailed(<u64>::max_value()),
+        ("18446744073709551615", false)
+    );
+    assert_eq!(buffer.format_detailed(<i8>::min_value()), ("-128", true));
+    assert_eq!(buffer.last(), Some("-128"));
+}
+
+#[test]
+fn test_binary() {
+    let mut buffer = itoa::WideBuffer::new();
+    for &n in &[0u64, 1, 2, 1 << 31, 1 << 32, 1 << 63, <u64>::max_value()] {
+        assert_eq!(buffer.format_binary(n), format!("{:b}", n));
+    }
+    assert_eq!(buffer.format_binary(-1i8), "11111111");
+    assert_eq!(buffer.format_binary(<i16>::min_value()), "1000000000000000");
+}
+
+#[test]
+fn test_hex_padded() {
+    let mut buffer = itoa::WideBuffer::new();
+    for &n in &[0u64, 1, 0xab, 1 << 32, <u64>::max_value()] {
+        assert_eq!(
+            buffer.format_hex_padded(n as u8),
+            format!("{:02x}", n as u8)
+        );
+        assert_eq!(
+            buffer.format_hex_padded(n as u16),
+            format!("{:04x}", n as u16)
+        );
+        assert_eq!(
+            buffer.format_hex_padded(n as u32),
+            format!("{:08x}", n as u32)
+        );
+        assert_eq!(buffer.format_hex_padded(n), format!("{:016x}", n));
+        assert_eq!(
+            buffer.format_hex_padded(n as usize),
+            format!("{:01$x}", n as usize, 2 * std::mem::size_of::<usize>())
+        );
+    }
+    assert_eq!(buffer.format_hex_padded(-1i16), "ffff");
+    for n in &[5u32, 0xdead_beef] {
+        assert_eq!(buffer.format_hex_padded(n), format!("{:08x}", n));
+    }
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_hex_padded_u128() {
+    let mut buffer = itoa::WideBuffer::new();
+    assert_eq!(buffer.format_hex_padded(1u128), format!("{:032x}", 1u128));
+    assert_eq!(
+        buffer.format_hex_padded(<i128>::min_value()),
+        format!("{:032x}", <i128>::min_value())
+    );
+}
+
+#[test]
+fn test_octal() {
+    let mut buffer = itoa::WideBuffer::new();
+    for &n in &[0u64, 1, 8, 1 << 31, 1 << 32, 1 << 63, <u64>::max_value()] {
+        assert_eq!(buffer.format_octal(n), format!("{:o}", n));
+    }
+    assert_eq!(buffer.format_octal(-1i8), "377");
+    assert_eq!(buffer.format_octal(<i32>::min_value()), "20000000000");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_binary_octal_u128() {
+    let mut buffer = itoa::WideBuffer::new();
+    for &n in &[0u128, 1, 1 << 64, 1 << 127, <u128>::max_value()] {
+        assert_eq!(buffer.format_binary(n), format!("{:b}", n));
+        assert_eq!(buffer.format_octal(n), format!("{:o}", n));
+    }
+    let n = <i128>::min_value();
+    assert_eq!(buffer.format_binary(n), format!("{:b}", n));
+    assert_eq!(buffer.format_octal(n), format!("{:o}", n));
+}
+
+#[test]
+fn test_prefixed() {
+    let mut buffer = itoa::WideBuffer::new();
+    for &n in &[0u64, 1, 8, 255, 1 << 32, <u64>::max_value()] {
+        assert_eq!(buffer.format_hex_prefixed(n), format!("{:#x}", n));
+        assert_eq!(buffer.format_binary_prefixed(n), format!("{:#b}", n));
+        assert_eq!(buffer.format_octal_prefixed(n), format!("{:#o}", n));
+    }
+    // Signed values print their bit pattern, with no sign before the prefix.
+    assert_eq!(buffer.format_hex_prefixed(-1i8), "0xff");
+    assert_eq!(buffer.format_binary_prefixed(-2i8), "0b11111110");
+    assert_eq!(buffer.format_octal_prefixed(-1i16), format!("{:#o}", -1i16));
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_prefixed_u128() {
+    let mut buffer = itoa::WideBuffer::new();
+    let n = <u128>::max_value();
+    assert_eq!(buffer.format_binary_prefixed(n), format!("{:#b}", n));
+    let n = <i128>::min_value();
+    assert_eq!(buffer.format_binary_prefixed(n), format!("{:#b}", n));
+    assert_eq!(buffer.format_hex_prefixed(n), format!("{:#x}", n));
+}
+
+#[test]
+fn test_radix() {
+    let mut buffer = itoa::WideBuffer::new();
+    for radix in 2..37 {
+        for &n in &[0u64, 1, 35, 36, 1 << 32, <u64>::max_value()] {
+            let s = buffer.format_radix(n, radix);
+            assert_eq!(u64::from_str_radix(s, radix), Ok(n));
+        }
+        for &n in &[-1i64, <i64>::min_value(), <i64>::max_value()] {
+            let s = buffer.format_radix(n, radix);
+            assert_eq!(i64::from_str_radix(s, radix), Ok(n));
+        }
+    }
+    assert_eq!(buffer.format_radix(35u8, 36), "z");
+    assert_eq!(buffer.format_radix(-255i16, 16), "-ff");
+    assert_eq!(buffer.format_radix(<i8>::min_value(), 2), "-10000000");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_radix_u128() {
+    let mut buffer = itoa::WideBuffer::new();
+    for radix in 2..37 {
+        for &n in &[
+            0u128,
+            1,
+            1 << 64,
+            <u64>::max_value() as u128,
+            <u128>::max_value(),
+        ] {
+            let s = buffer.format_radix(n, radix);
+            assert_eq!(u128::from_str_radix(s, radix), Ok(n));
+        }
+        let n = <i128>::min_value();
+        let s = buffer.format_radix(n, radix);
+        assert_eq!(i128::from_str_radix(s, radix), Ok(n));
+    }
+    let s = buffer.format_radix(<i128>::min_value(), 2);
+    assert_eq!(s.len(), 129);
+}
+
+#[test]
+fn test_base36() {
+    let mut buffer = itoa::WideBuffer::new();
+    for &n in &[0u64, 35, 36, 1295, 1296, 1 << 40, <u64>::max_value()] {
+        let s = buffer.format_base36(n).to_owned();
+        assert_eq!(u64::from_str_radix(&s, 36), Ok(n));
+        let upper = buffer.format_base36_upper(n);
+        assert_eq!(u64::from_str_radix(upper, 36), Ok(n));
+        assert_eq!(upper, s.to_uppercase());
+    }
+    assert_eq!(buffer.format_base36(<u64>::max_value()), "3w5e11264sgsf");
+    assert_eq!(buffer.format_base36_upper(-35i8), "-Z");
+}
+
+#[test]
+#[should_panic]
+fn test_radix_too_large() {
+    itoa::WideBuffer::new().format_radix(1u32, 37);
+}
+
+#[test]
+#[should_panic]
+fn test_radix_too_small() {
+    itoa::WideBuffer::new().format_radix(1u32, 1);
+}
+
+#[test]
+fn test_padded() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_padded(7u32, 4), "0007");
+    assert_eq!(buffer.format_padded(0u8, 3), "000");
+    assert_eq!(buffer.format_padded(-42i32, 5), "-0042");
+    assert_eq!(buffer.format_padded(-42i32, 3), "-42");
+    assert_eq!(buffer.format_padded(-42i32, 2), "-42");
+    assert_eq!(buffer.format_padded(12345u16, 3), "12345");
+    assert_eq!(buffer.format_padded(12345u16, 0), "12345");
+    assert_eq!(
+        buffer.format_padded(<i64>::min_value(), 22),
+        "-009223372036854775808"
+    );
+    let s = buffer.format_padded(-1i8, 1000);
+    assert_eq!(s.len(), 40);
+    assert!(s.starts_with("-000"));
+    assert!(s.ends_with("0001"));
+}
+
+#[test]
+fn test_fixed_point() {
+    let mut buffer = itoa::WideBuffer::new();
+    assert_eq!(buffer.format_fixed_point(12345u32, 2), "123.45");
+    assert_eq!(buffer.format_fixed_point(-12345i32, 2), "-123.45");
+    assert_eq!(buffer.format_fixed_point(5u32, 2), "0.05");
+    assert_eq!(buffer.format_fixed_point(-5i32, 2), "-0.05");
+    assert_eq!(buffer.format_fixed_point(42u32, 2), "0.42");
+    assert_eq!(buffer.format_fixed_point(-42i32, 2), "-0.42");
+    assert_eq!(buffer.format_fixed_point(0u8, 3), "0.000");
+    assert_eq!(buffer.format_fixed_point(-7i8, 0), "-7");
+    assert_eq!(buffer.format_fixed_point(100u8, 1), "10.0");
+    assert_eq!(
+        buffer.format_fixed_point(<i64>::min_value(), 18),
+        "-9.223372036854775808"
+    );
+
+    let s = buffer.format_fixed_point(-1i8, 127);
+    assert_eq!(s.len(), 130);
+    assert!(s.starts_with("-0.000"));
+    assert!(s.ends_with("0001"));
+}
+
+#[test]
+fn test_fixed_point_too_many_digits() {
+    let mut buffer = itoa::WideBuffer::new();
+    let expected = buffer.format_fixed_point(-1i8, 127).to_owned();
+    assert_eq!(buffer.format_fixed_point(-1i8, 128), expected);
+    assert_eq!(
+        buffer.format_fixed_point(-1i8, <usize>::max_value()),
+        expected
+    );
+    let s = buffer.format_fixed_point(<i64>::min_value(), 1000);
+    assert_eq!(s.len(), 130);
+    assert!(s.ends_with("9223372036854775808"));
+}
+
+#[test]
+fn test_right_aligned() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_right_aligned(7u32, 4, b' '), "   7");
+    assert_eq!(buffer.format_right_aligned(-42i32, 5, b' '), "  -42");
+    assert_eq!(buffer.format_right_aligned(-42i32, 5, b'*'), "**-42");
+    assert_eq!(buffer.format_right_aligned(-42i32, 3, b' '), "-42");
+    assert_eq!(buffer.format_right_aligned(12345u16, 3, b' '), "12345");
+    let s = buffer.format_right_aligned(-1i8, 1000, b' ');
+    assert_eq!(s.len(), 40);
+    assert!(s.ends_with("  -1"));
+}
+
+#[test]
+fn test_try_format_padded() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.try_format_padded(-42i32, 5), Ok("-0042"));
+    assert_eq!(buffer.try_format_padded(12345u16, 3), Ok("12345"));
+    assert_eq!(buffer.try_format_padded(7u8, 40).map(str::len), Ok(40));
+    assert_eq!(
+        buffer.try_format_right_aligned(-42i32, 5, b' '),
+        Ok("  -42")
+    );
+
+    let err = buffer.try_format_padded(7u8, 41).unwrap_err();
+    assert_eq!(err.needed(), 41);
+    assert_eq!(err.capacity(), buffer.capacity());
+    assert_eq!(err.to_string(), "width of 41 exceeds buffer capacity of 40");
+
+    let err = buffer
+        .try_format_right_aligned(7u8, <usize>::max_value(), b' ')
+        .unwrap_err();
+    assert_eq!(err.needed(), <usize>::max_value());
+    assert_eq!(err.capacity(), 40);
+}
+
+#[test]
+#[should_panic]
+fn test_right_aligned_non_ascii() {
+    itoa::Buffer::new().format_right_aligned(1u8, 3, 0xA0);
+}
+
+#[test]
+fn test_field() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_field(-42i32, 3), "-42");
+    assert_eq!(buffer.format_field(-42i32, 6), "   -42");
+    assert_eq!(buffer.format_field(7u8, 1), "7");
+    assert_eq!(buffer.format_field(-42i32, 2), "**");
+    assert_eq!(buffer.format_field(<u64>::max_value(), 8), "********");
+    assert_eq!(buffer.format_field(5u8, 0), "");
+    assert_eq!(buffer.format_field(-1i8, 1000).len(), 40);
+}
+
+#[test]
+fn test_full_width() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_full_width(0u32), "0000000000");
+    assert_eq!(buffer.format_full_width(7u8), "007");
+    assert_eq!(buffer.format_full_width(-7i8), "-007");
+    assert_eq!(
+        buffer.format_full_width(<i64>::min_value()),
+        "-9223372036854775808"
+    );
+    assert_eq!(
+        buffer.format_full_width(<u64>::max_value()),
+        "18446744073709551615"
+    );
+    assert_eq!(
+        buffer.format_full_width(std::num::NonZeroU16::new(1).unwrap()),
+        "00001"
+    );
+}
+
+#[test]
+fn test_grouped() {
+    let mut buffer = itoa::WideBuffer::new();
+    assert_eq!(buffer.format_grouped(0u8, b',', 3), "0");
+    assert_eq!(buffer.format_grouped(123u32, b',', 3), "123");
+    assert_eq!(buffer.format_grouped(1234u32, b',', 3), "1,234");
+    assert_eq!(buffer.format_grouped(1234567u32, b',', 3), "1,234,567");
+    assert_eq!(buffer.format_grouped(123456u32, b',', 3), "123,456");
+    assert_eq!(buffer.format_grouped(-123456i32, b',', 3), "-123,456");
+    assert_eq!(buffer.format_grouped(-1234567i32, b',', 3), "-1,234,567");
+    assert_eq!(buffer.format_grouped(12345678u32, b'_', 4), "1234_5678");
+    assert_eq!(buffer.format_grouped(-123i8, b' ', 1), "-1 2 3");
+    assert_eq!(
+        buffer.format_grouped(<u64>::max_value(), b',', 3),
+        "18,446,744,073,709,551,615"
+    );
+    assert_eq!(
+        buffer.format_grouped(<i64>::min_value(), b',', 3),
+        "-9,223,372,036,854,775,808"
+    );
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_grouped_i128() {
+    let mut buffer = itoa::WideBuffer::new();
+    let s = buffer.format_grouped(<i128>::min_value(), b',', 1);
+    assert_eq!(s.len(), 78);
+    assert_eq!(s.replace(",", ""), format!("{}", <i128>::min_value()));
+}
+
+#[test]
+fn test_buffer_grouped() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_grouped(0u8, b',', 3), "0");
+    assert_eq!(buffer.format_grouped(-1234567i32, b',', 3), "-1,234,567");
+    assert_eq!(
+        buffer.format_grouped(<u64>::max_value(), b',', 3),
+        "18,446,744,073,709,551,615"
+    );
+    assert_eq!(
+        buffer.format_grouped(<i64>::min_value(), b',', 3),
+        "-9,223,372,036,854,775,808"
+    );
+    let s = buffer.format_grouped(<u64>::max_value(), b' ', 1);
+    assert_eq!(s.len(), 39);
+    assert_eq!(s.replace(" ", ""), <u64>::max_value().to_string());
+    let s = buffer.format_grouped(<i64>::min_value(), b' ', 1);
+    assert_eq!(s.replace(" ", ""), <i64>::min_value().to_string());
+}
+
+#[cfg(feature = "i128")]
+#[test]
+#[should_panic]
+fn test_buffer_grouped_i128() {
+    itoa::Buffer::new().format_grouped(1i128, b',', 3);
+}
+
+#[test]
+#[should_panic]
+fn test_grouped_non_ascii_separator() {
+    itoa::WideBuffer::new().format_grouped(1234u32, 0xA0, 3);
+}
+
+#[test]
+fn test_grouped_indian() {
+    let mut buffer = itoa::WideBuffer::new();
+    assert_eq!(buffer.format_grouped_indian(0u8, b','), "0");
+    assert_eq!(buffer.format_grouped_indian(100u32, b','), "100");
+    assert_eq!(buffer.format_grouped_indian(1000u32, b','), "1,000");
+    assert_eq!(buffer.format_grouped_indian(100000u32, b','), "1,00,000");
+    assert_eq!(buffer.format_grouped_indian(1234567u32, b','), "12,34,567");
+    assert_eq!(
+        buffer.format_grouped_indian(-1234567i32, b','),
+        "-12,34,567"
+    );
+    assert_eq!(buffer.format_grouped_indian(-100i32, b','), "-100");
+    assert_eq!(
+        buffer.format_grouped_indian(<i64>::min_value(), b','),
+        "-92,23,37,20,36,85,47,75,808"
+    );
+}
+
+#[test]
+fn test_signed() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_signed(0i32), "+0");
+    assert_eq!(buffer.format_signed(0u32), "+0");
+    assert_eq!(buffer.format_signed(5i32), "+5");
+    assert_eq!(buffer.format_signed(-5i32), "-5");
+    assert_eq!(buffer.format_signed(<i8>::max_value()), "+127");
+    assert_eq!(buffer.format_signed(<u8>::max_value()), "+255");
+    assert_eq!(buffer.format_signed(<i16>::max_value()), "+32767");
+    assert_eq!(buffer.format_signed(<u16>::max_value()), "+65535");
+    assert_eq!(buffer.format_signed(<i32>::max_value()), "+2147483647");
+    assert_eq!(buffer.format_signed(<u32>::max_value()), "+4294967295");
+    assert_eq!(
+        buffer.format_signed(<i64>::max_value()),
+        "+9223372036854775807"
+    );
+    assert_eq!(
+        buffer.format_signed(<u64>::max_value()),
+        "+18446744073709551615"
+    );
+    assert_eq!(
+        buffer.format_signed(<i64>::min_value()),
+        "-9223372036854775808"
+    );
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_signed_i128() {
+    let mut buffer = itoa::Buffer::new();
+    let n = <i128>::max_value();
+    assert_eq!(buffer.format_signed(n), format!("{:+}", n));
+    let n = <u128>::max_value();
+    assert_eq!(buffer.format_signed(n), format!("{:+}", n));
+    let n = <i128>::min_value();
+    assert_eq!(buffer.format_signed(n), format!("{:+}", n));
+}
+
+#[test]
+fn test_abs() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_abs(<i8>::min_value()), "128");
+    assert_eq!(buffer.format_abs(<i16>::min_value()), "32768");
+    assert_eq!(buffer.format_abs(<i32>::min_value()), "2147483648");
+    assert_eq!(buffer.format_abs(<i64>::min_value()), "9223372036854775808");
+    assert_eq!(
+        buffer.format_abs(<isize>::min_value()),
+        (<isize>::min_value() as i128).abs().to_string()
+    );
+    assert_eq!(buffer.format_abs(-1i32), "1");
+    assert_eq!(buffer.format_abs(0i32), "0");
+    assert_eq!(buffer.format_abs(42i32), "42");
+    assert_eq!(
+        buffer.format_abs(<u64>::max_value()),
+        "18446744073709551615"
+    );
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_abs_i128() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(
+        buffer.format_abs(<i128>::min_value()),
+        "170141183460469231731687303715884105728"
+    );
+}
+
+#[test]
+fn test_reversed() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_reversed(120), "021");
+    assert_eq!(buffer.format_reversed(-120), "-021");
+    assert_eq!(buffer.format_reversed(0u8), "0");
+    for &n in &[7u64, 10, 12345, 1 << 40, <u64>::max_value()] {
+        let forward: String = buffer.format(n).chars().rev().collect();
+        assert_eq!(buffer.format_reversed(n), forward);
+    }
+}
+
+#[test]
+fn test_with_sign() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_with_sign(-42i32, b'_', None), "_42");
+    assert_eq!(buffer.format_with_sign(42i32, b'_', None), "42");
+    assert_eq!(buffer.format_with_sign(0u8, b'_', None), "0");
+    assert_eq!(buffer.format_with_sign(-42i32, b'_', Some(b'^')), "_42");
+    assert_eq!(buffer.format_with_sign(42i32, b'_', Some(b'^')), "^42");
+    assert_eq!(buffer.format_with_sign(0u8, b'_', Some(b'^')), "^0");
+    assert_eq!(
+        buffer.format_with_sign(<i64>::min_value(), b'_', Some(b'^')),
+        "_9223372036854775808"
+    );
+}
+
+#[test]
+fn test_padded_constant_time() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_padded_constant_time(42u16), "00042");
+    assert_eq!(buffer.format_padded_constant_time(-42i16), "-00042");
+    assert_eq!(buffer.format_padded_constant_time(0u8), "000");
+    assert_eq!(
+        buffer.format_padded_constant_time(<i8>::min_value()),
+        "-128"
+    );
+    assert_eq!(
+        buffer.format_padded_constant_time(1u64),
+        "00000000000000000001"
+    );
+
+    let mut rng = Rng(0x4f28_cb3e_6a1d_9075);
+    for _ in 0..10_000 {
+        let n = rng.next() as i64 >> (rng.next() % 64);
+        let expected = buffer.format(n).to_owned();
+        let padded = buffer.format_padded_constant_time(n);
+        assert_eq!(padded.len(), if n < 0 { 20 } else { 19 });
+        let (sign, digits) = padded.split_at(if n < 0 { 1 } else { 0 });
+        let digits = digits.trim_start_matches('0');
+        let digits = if digits.is_empty() { "0" } else { digits };
+        assert_eq!(format!("{}{}", sign, digits), expected);
+    }
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_padded_constant_time_i128() {
+    let mut buffer = itoa::Buffer::new();
+    let n = <i128>::min_value();
+    assert_eq!(buffer.format_padded_constant_time(n), n.to_string());
+    let s = buffer.format_padded_constant_time(7u128);
+    assert_eq!(s.len(), 39);
+    assert!(s.ends_with("007"));
+    assert_eq!(
+        buffer.format_padded_constant_time(<u128>::max_value()),
+        <u128>::max_value().to_string()
+    );
+
+    // The digits are formatted in chunks of 19.
+    let mut rng = Rng(0x2d35_8dcc_aa6c_78a5);
+    for _ in 0..10_000 {
+        let bits = (rng.next() as u128) << 64 | rng.next() as u128;
+        let n = bits >> (rng.next() % 128);
+        let padded = buffer.format_padded_constant_time(n);
+        assert_eq!(padded.len(), 39);
+        assert_eq!(padded.parse(), Ok(n));
+        let n = n as i128;
+        let padded = buffer.format_padded_constant_time(n);
+        assert_eq!(padded.len(), if n < 0 { 40 } else { 39 });
+        assert_eq!(padded.parse(), Ok(n));
+    }
+}
+
+#[test]
+fn test_accounting() {
+    let mut buffer = itoa::WideBuffer::new();
+    assert_eq!(buffer.format_accounting(-42i32), "(42)");
+    assert_eq!(buffer.format_accounting(42i32), "42");
+    assert_eq!(buffer.format_accounting(0i32), "0");
+    assert_eq!(buffer.format_accounting(-1i8), "(1)");
+    assert_eq!(
+        buffer.format_accounting(<i64>::min_value()),
+        "(9223372036854775808)"
+    );
+}
+
+#[test]
+fn test_clock() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_clock(0), "00:00:00");
+    assert_eq!(buffer.format_clock(59), "00:00:59");
+    assert_eq!(buffer.format_clock(3599), "00:59:59");
+    assert_eq!(buffer.format_clock(3661), "01:01:01");
+    assert_eq!(buffer.format_clock(86399), "23:59:59");
+    assert_eq!(buffer.format_clock(360000), "100:00:00");
+    assert_eq!(
+        buffer.format_clock(<u64>::max_value()),
+        "5124095576030431:00:15"
+    );
+}
+
+#[test]
+fn test_compact() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_compact(1000000u32), "1e6");
+    assert_eq!(buffer.format_compact(1200000u32), "12e5");
+    assert_eq!(buffer.format_compact(-1000i32), "-1e3");
+    assert_eq!(buffer.format_compact(1234u32), "1234");
+    assert_eq!(buffer.format_compact(100u32), "100");
+    assert_eq!(buffer.format_compact(0u32), "0");
+    assert_eq!(buffer.format_compact(10000000000u64), "1e10");
+    assert_eq!(
+        buffer.format_compact(<u64>::max_value()),
+        "18446744073709551615"
+    );
+    assert_eq!(buffer.last(), Some("18446744073709551615"));
+}
+
+#[test]
+#[should_panic]
+fn test_with_sign_non_ascii() {
+    itoa::Buffer::new().format_with_sign(-1i8, 0xE2, None);
+}
+
+#[test]
+fn test_fmt_counting_writer() {
+    use std::fmt;
+
+    struct Counter(usize);
+
+    impl fmt::Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.len();
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    itoa::fmt(&mut counter, 0u8).unwrap();
+    itoa::fmt(&mut counter, -128i8).unwrap();
+    itoa::fmt(&mut counter, <u64>::max_value()).unwrap();
+    assert_eq!(counter.0, 1 + 4 + 20);
+
+    let mut s = String::from("x = ");
+    itoa::fmt(&mut s, -1i32).unwrap();
+    assert_eq!(s, "x = -1");
+}
+
+#[test]
+fn test_counting_writer() {
+    use std::fmt::Write;
+
+    let values = [0i64, -1, 42, <i64>::min_value(), <i64>::max_value()];
+    let mut writer = itoa::CountingWriter::new(String::new());
+    let mut expected = 0;
+    for &n in &values {
+        itoa::fmt(&mut writer, n).unwrap();
+        writer.write_str(", ").unwrap();
+        expected += itoa::written_len(n) + 2;
+    }
+    assert_eq!(writer.count(), expected);
+    assert_eq!(writer.get_ref().len(), expected);
+
+    let mut writer = itoa::CountingWriter::new(String::new());
+    itoa::join_into(&mut writer, &values, ",").unwrap();
+    assert_eq!(writer.count(), writer.into_inner().len());
+}
+
+#[test]
+fn test_slice_writer() {
+    use std::fmt::Write;
+
+    let mut bytes = [0u8; 20];
+    let mut writer = itoa::SliceWriter::new(&mut bytes);
+    itoa::fmt(&mut writer, <u64>::max_value()).unwrap();
+    assert_eq!(writer.written(), b"18446744073709551615");
+    assert_eq!(writer.write_str(""), Ok(()));
+
+    let mut bytes = [0u8; 6];
+    let mut writer = itoa::SliceWriter::new(&mut bytes);
+    itoa::fmt(&mut writer, -128i8).unwrap();
+    assert!(itoa::fmt(&mut writer, 123u8).is_err());
+    assert_eq!(writer.written(), b"-128");
+    itoa::fmt(&mut writer, 12u8).unwrap();
+    assert_eq!(writer.written(), b"-12812");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_vec() {
+    let mut buf = Vec::new();
+    assert_eq!(itoa::write(&mut buf, 128u64).unwrap(), 3);
+    assert_eq!(itoa::write(&mut buf, -1i8).unwrap(), 2);
+    assert_eq!(buf, b"128-1");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_len_prefixed() {
+    use std::str::{self, FromStr};
+
+    let values = [0i64, 7, -42, <i64>::min_value(), <i64>::max_value()];
+    let mut wire = Vec::new();
+    for &n in &values {
+        itoa::write_len_prefixed(&mut wire, n).unwrap();
+    }
+
+    let mut rest = &wire[..];
+    for &n in &values {
+        let len = rest[0] as usize;
+        let body = &rest[1..1 + len];
+        assert_eq!(i64::from_str(str::from_utf8(body).unwrap()), Ok(n));
+        rest = &rest[1 + len..];
+    }
+    assert!(rest.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_vectored() {
+    use std::io::{self, IoSlice};
+
+    let mut wire = Vec::new();
+    let mut expected = Vec::new();
+    for &n in &[0i64, -42, <i64>::min_value(), <i64>::max_value()] {
+        itoa::write_vectored(&mut wire, n).unwrap();
+        itoa::write_len_prefixed(&mut expected, n).unwrap();
+    }
+    assert_eq!(wire, expected);
+
+    // Takes at most 2 bytes per call, spanning slices, and is interrupted
+    // before every successful write.
+    struct Trickle {
+        out: Vec<u8>,
+        interrupt: bool,
+    }
+
+    impl io::Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.write_vectored(&[IoSlice::new(buf)])
+        }
+
+        fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "again"));
+            }
+            let mut n = 0;
+            for &b in bufs.iter().flat_map(|buf| buf.iter()).take(2) {
+                self.out.push(b);
+                n += 1;
+            }
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut trickle = Trickle {
+        out: Vec::new(),
+        interrupt: false,
+    };
+    itoa::write_vectored(&mut trickle, 12345u32).unwrap();
+    itoa::write_vectored(&mut trickle, 7u8).unwrap();
+    assert_eq!(trickle.out, b"\x0512345\x017");
+
+    let err = itoa::write_vectored(&mut &mut [0u8; 3][..], 12345u32).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_writeln() {
+    use std::io;
+
+    let values = [0i64, 7, -42, <i64>::min_value(), <i64>::max_value()];
+    let mut out = Vec::new();
+    for &n in &values {
+        itoa::writeln(&mut out, n).unwrap();
+    }
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.ends_with('\n'));
+    let parsed: Vec<i64> = text.lines().map(|line| line.parse().unwrap()).collect();
+    assert_eq!(parsed, values);
+
+    // Counts the calls, to check that each line is one write_all.
+    struct Calls(usize, Vec<u8>);
+
+    impl io::Write for Calls {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += 1;
+            self.1.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut calls = Calls(0, Vec::new());
+    itoa::writeln(&mut calls, <u64>::max_value()).unwrap();
+    assert_eq!(calls.0, 1);
+    assert_eq!(calls.1, b"18446744073709551615\n");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_write_error() {
+    use std::io;
+
+    // Accepts a few bytes, then fails.
+    struct Failing(usize);
+
+    impl io::Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 == 0 {
+                return Err(io::Error::new(io::ErrorKind::Other, "full"));
+            }
+            let n = std::cmp::min(self.0, buf.len());
+            self.0 -= n;
+            Ok(n)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = itoa::write(Failing(3), 123456u32).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+    assert_eq!(itoa::write(Failing(3), 123u32).unwrap(), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_push_to_vec() {
+    let mut vec = b"[".to_vec();
+    itoa::push_to_vec(&mut vec, 1u8);
+    vec.push(b',');
+    itoa::push_to_vec(&mut vec, -20i32);
+    vec.push(b',');
+    itoa::push_to_vec(&mut vec, <u64>::max_value());
+    vec.push(b']');
+    assert_eq!(vec, b"[1,-20,18446744073709551615]");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_push_to_string() {
+    let mut s = String::with_capacity(4);
+    let mut expected = String::new();
+    let mut buffer = itoa::Buffer::new();
+    for &n in &[0i64, -1, 42, <i64>::min_value(), <i64>::max_value()] {
+        itoa::push_to_string(&mut s, n);
+        s.push(' ');
+        expected.push_str(buffer.format(n));
+        expected.push(' ');
+    }
+    assert_eq!(s, expected);
+
+    // A string with room for the longest value is written in place.
+    let mut s = String::with_capacity(2 + itoa::MAX_LEN);
+    s.push_str("n=");
+    let capacity = s.capacity();
+    itoa::push_to_string(&mut s, -1234567);
+    assert_eq!(s, "n=-1234567");
+    assert_eq!((s.len(), s.capacity()), (10, capacity));
+    itoa::push_to_string(&mut s, <i64>::min_value());
+    assert_eq!(s, "n=-1234567-9223372036854775808");
+    assert_eq!((s.len(), s.capacity()), (30, capacity));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_concat() {
+    for &n in &[0i64, -1, 12345, <i64>::min_value(), <i64>::max_value()] {
+        let s = itoa::concat("user:", n);
+        assert_eq!(s, String::from("user:") + &n.to_string());
+        // The one allocation is not grown by formatting the digits.
+        assert_eq!(
+            s.capacity(),
+            "user:".len() + <i64 as itoa::Integer>::MAX_STR_LEN
+        );
+    }
+    assert_eq!(itoa::concat("", 7u8), "7");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_format_cow() {
+    use std::borrow::Cow;
+
+    for n in 0..=255u32 {
+        match itoa::format_cow(n) {
+            Cow::Borrowed(s) => assert_eq!(s, n.to_string()),
+            Cow::Owned(s) => panic!("{} was not cached", s),
+        }
+    }
+    assert!(match itoa::format_cow(7u8) {
+        Cow::Borrowed(_) => true,
+        Cow::Owned(_) => false,
+    });
+    for &n in &[
+        256i64,
+        -1,
+        -255,
+        1000,
+        <i64>::min_value(),
+        <i64>::max_value(),
+    ] {
+        match itoa::format_cow(n) {
+            Cow::Borrowed(s) => panic!("{} was cached", s),
+            Cow::Owned(s) => assert_eq!(s, n.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_join_to_vec() {
+    let mut out = Vec::new();
+    itoa::join_to_vec(&[] as &[u64], b',', &mut out);
+    assert!(out.is_empty());
+    assert_eq!(out.capacity(), 0);
+
+    let values = [0i32, -1, 42, <i32>::min_value(), <i32>::max_value()];
+    let mut out = b"xs=".to_vec();
+    itoa::join_to_vec(&values, b',', &mut out);
+    assert_eq!(&out[..], &b"xs=0,-1,42,-2147483648,2147483647"[..]);
+
+    let mut out = Vec::new();
+    itoa::join_to_vec(&values, b' ', &mut out);
+    let capacity = out.capacity();
+    assert!(capacity >= values.len() * 11 + values.len() - 1);
+    let parsed: Vec<i32> = std::str::from_utf8(&out)
+        .unwrap()
+        .split(' ')
+        .map(|s| s.parse().unwrap())
+        .collect();
+    assert_eq!(parsed, values);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_format_all() {
+    use std::str::{self, FromStr};
+
+    let (bytes, offsets) = itoa::format_all(&[]);
+    assert!(bytes.is_empty());
+    assert_eq!(offsets, [0]);
+
+    let values = [0u64, 9, 10, 12345, <u64>::max_value(), 1];
+    let (bytes, offsets) = itoa::format_all(&values);
+    assert_eq!(offsets.len(), values.len() + 1);
+    assert_eq!(offsets[values.len()] as usize, bytes.len());
+    for (i, &n) in values.iter().enumerate() {
+        let slice = &bytes[offsets[i] as usize..offsets[i + 1] as usize];
+        assert_eq!(u64::from_str(str::from_utf8(slice).unwrap()), Ok(n));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_format_column() {
+    assert!(itoa::format_column(&[]).is_empty());
+
+    let mut rng = Rng(0x1d3c_59e2_7a08_b46f);
+    let mut values: Vec<i64> = (0..1000)
+        .map(|_| rng.next() as i64 >> (rng.next() % 64))
+        .collect();
+    values.extend_from_slice(&[0, -1, <i64>::min_value(), <i64>::max_value()]);
+    let mut buffer = itoa::Buffer::new();
+    let naive: Vec<String> = values
+        .iter()
+        .map(|&n| buffer.format(n).to_owned())
+        .collect();
+    let column = itoa::format_column(&values);
+    assert_eq!(column, naive);
+    for s in &column {
+        assert_eq!(s.capacity(), s.len());
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "i128"))]
+#[test]
+fn test_push_to_vec_i128() {
+    let mut vec = Vec::new();
+    itoa::push_to_vec(&mut vec, <i128>::min_value());
+    itoa::push_to_vec(&mut vec, 0u128);
+    assert_eq!(vec, &b"-1701411834604692317316873037158841057280"[..]);
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_put_bytes_mut() {
+    use bytes::BufMut;
+
+    let mut buf = bytes::BytesMut::with_capacity(8);
+    buf.put_slice(b"n=");
+    itoa::put(&mut buf, 42u8);
+    buf.put_u8(b' ');
+    itoa::put(&mut buf, <i64>::min_value());
+    buf.put_u8(b' ');
+    itoa::put(&mut buf, 0usize);
+    assert_eq!(&buf[..], &b"n=42 -9223372036854775808 0"[..]);
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_extend_smallvec() {
+    let mut sv = smallvec::SmallVec::<[u8; 32]>::new();
+    itoa::extend_smallvec(&mut sv, -42i32);
+    sv.push(b',');
+    itoa::extend_smallvec(&mut sv, <u64>::max_value());
+    assert!(!sv.spilled());
+    assert_eq!(&sv[..], &b"-42,18446744073709551615"[..]);
+
+    itoa::extend_smallvec(&mut sv, <i64>::min_value());
+    assert!(sv.spilled());
+    assert_eq!(
+        &sv[..],
+        &b"-42,18446744073709551615-9223372036854775808"[..]
+    );
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn test_extend_heapless() {
+    let mut vec = heapless::Vec::<u8, 8>::new();
+    assert_eq!(itoa::extend_heapless(&mut vec, -1234i32), Ok(()));
+    assert_eq!(itoa::extend_heapless(&mut vec, 567u16), Ok(()));
+    assert_eq!(&vec[..], b"-1234567");
+
+    let mut vec = heapless::Vec::<u8, 5>::new();
+    vec.push(b'x').unwrap();
+    assert_eq!(itoa::extend_heapless(&mut vec, 12345u32), Err(()));
+    assert_eq!(&vec[..], b"x");
+    assert_eq!(itoa::extend_heapless(&mut vec, 1234u32), Ok(()));
+    assert_eq!(&vec[..], b"x1234");
+}
+
+#[test]
+fn test_nonzero() {
+    use std::num::{NonZeroI32, NonZeroU64, NonZeroU8};
+
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format(NonZeroU8::new(1).unwrap()), "1");
+    assert_eq!(buffer.format(NonZeroI32::new(-42).unwrap()), "-42");
+    assert_eq!(
+        buffer.format(NonZeroU64::new(<u64>::max_value()).unwrap()),
+        "18446744073709551615"
+    );
+    assert_eq!(
+        itoa::WideBuffer::new().format_binary(NonZeroU8::new(5).unwrap()),
+        "101"
+    );
+
+    let mut s = String::new();
+    itoa::fmt(&mut s, NonZeroI32::new(<i32>::min_value()).unwrap()).unwrap();
+    assert_eq!(s, "-2147483648");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_nonzero_i128() {
+    use std::num::{NonZeroI128, NonZeroU128};
+
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(
+        buffer.format(NonZeroU128::new(<u128>::max_value()).unwrap()),
+        "340282366920938463463374607431768211455"
+    );
+    assert_eq!(
+        buffer.format(NonZeroI128::new(<i128>::min_value()).unwrap()),
+        "-170141183460469231731687303715884105728"
+    );
+}
+
+#[test]
+fn test_wrapping() {
+    use std::num::Wrapping;
+
+    let mut buffer = itoa::Buffer::new();
+    let n = Wrapping(255u8) + Wrapping(1);
+    assert_eq!(buffer.format(n), "0");
+    assert_eq!(buffer.format(Wrapping(255u8)), "255");
+    assert_eq!(
+        buffer.format(Wrapping(<i64>::min_value())),
+        "-9223372036854775808"
+    );
+}
+
+#[cfg(not(no_core_num_saturating))]
+#[test]
+fn test_saturating() {
+    use std::num::Saturating;
+
+    let mut buffer = itoa::Buffer::new();
+    let n = Saturating(<i32>::min_value()) - Saturating(1);
+    assert_eq!(buffer.format(n), "-2147483648");
+    assert_eq!(buffer.format(Saturating(200u8)), "200");
+}
+
+#[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
+fn test_reference() {
+    let mut buffer = itoa::Buffer::new();
+    let values = [0i32, -1, <i32>::max_value(), <i32>::min_value()];
+    for n in &values {
+        let expected = itoa::Buffer::new().format(*n).to_owned();
+        assert_eq!(buffer.format(n), expected);
+        assert_eq!(buffer.format(&n), expected);
+    }
+
+    let mut s = String::new();
+    itoa::fmt(&mut s, &10u8).unwrap();
+    assert_eq!(s, "10");
+}
+
+#[cfg(not(no_const_fn_trait_bound))]
+#[test]
+fn test_max_len() {
+    use std::num::{NonZeroU64, Wrapping};
+
+    const U64_LEN: usize = itoa::max_len::<u64>();
+    let array = [0u8; itoa::max_len::<i8>()];
+    assert_eq!(array.len(), 4);
+    assert_eq!(U64_LEN, 20);
+    assert_eq!(itoa::max_len::<i64>(), 20);
+    assert_eq!(itoa::max_len::<u16>(), 5);
+    assert_eq!(itoa::max_len::<NonZeroU64>(), 20);
+    assert_eq!(itoa::max_len::<Wrapping<i32>>(), 11);
+    assert_eq!(itoa::max_len::<&u8>(), 3);
+    assert_eq!(
+        itoa::max_len::<i32>(),
+        itoa::written_len(<i32>::min_value())
+    );
+}
+
+#[test]
+fn test_typed_buffer() {
+    use std::mem;
+    use std::num::NonZeroU16;
+
+    let mut buffer = itoa::TypedBuffer::<u8>::new();
+    assert_eq!(mem::size_of_val(&buffer), 3);
+    assert_eq!(buffer.format(0), "0");
+    assert_eq!(buffer.format(255), "255");
+
+    let mut buffer = itoa::TypedBuffer::<u16>::default();
+    assert_eq!(mem::size_of_val(&buffer), 5);
+    assert_eq!(buffer.format(65535), "65535");
+
+    let mut buffer = itoa::TypedBuffer::<i64>::new();
+    assert_eq!(mem::size_of_val(&buffer), 20);
+    assert_eq!(buffer.format(<i64>::min_value()), "-9223372036854775808");
+
+    let mut buffer = itoa::TypedBuffer::<NonZeroU16>::new();
+    assert_eq!(mem::size_of_val(&buffer), 5);
+    assert_eq!(buffer.format(NonZeroU16::new(42).unwrap()), "42");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_typed_buffer_i128() {
+    let mut buffer = itoa::TypedBuffer::<i128>::new();
+    assert_eq!(std::mem::size_of_val(&buffer), 40);
+    assert_eq!(
+        buffer.format(<i128>::min_value()),
+        "-170141183460469231731687303715884105728"
+    );
+    assert_eq!(buffer.format(-1), "-1");
+}
+
+#[cfg(not(no_target_has_atomic))]
+#[test]
+fn test_atomic() {
+    use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+
+    let mut buffer = itoa::Buffer::new();
+    let n = AtomicI32::new(0);
+    assert_eq!(buffer.format(&n), "0");
+    n.store(-1234, Ordering::SeqCst);
+    assert_eq!(buffer.format(&n), "-1234");
+    assert_eq!(itoa::WideBuffer::new().format_hex_padded(&n), "fffffb2e");
+
+    let count = AtomicUsize::new(41);
+    count.fetch_add(1, Ordering::SeqCst);
+    let mut s = String::new();
+    itoa::fmt(&mut s, &count).unwrap();
+    assert_eq!(s, "42");
+}
+
+#[test]
+fn test_signed_const() {
+    use itoa::Integer;
+    use std::num::{NonZeroU32, Wrapping};
+
+    macro_rules! assert_signed {
+        ($($t:ty => $signed:expr),*) => {$(
+            assert_eq!(<$t as Integer>::SIGNED, $signed, stringify!($t));
+        )*};
+    }
+
+    assert_signed! {
+        i8 => true, u8 => false,
+        i16 => true, u16 => false,
+        i32 => true, u32 => false,
+        i64 => true, u64 => false,
+        isize => true, usize => false,
+        NonZeroU32 => false, Wrapping<i64> => true, &i16 => true
+    }
+
+    #[cfg(feature = "i128")]
+    assert_signed!(i128 => true, u128 => false);
+}
+
+#[test]
+fn test_min_max_str() {
+    use itoa::Integer;
+    use std::num::{NonZeroI32, NonZeroU64, Wrapping};
+
+    macro_rules! assert_min_max {
+        ($($t:ident),*) => {$(
+            let mut buffer = itoa::Buffer::new();
+            assert_eq!(<$t as Integer>::MIN_STR, buffer.format(<$t>::min_value()));
+            assert_eq!(<$t as Integer>::MAX_STR, buffer.format(<$t>::max_value()));
+        )*};
+    }
+
+    assert_min_max!(i8, u8, i16, u16, i32, u32, i64, u64, isize, usize);
+    #[cfg(feature = "i128")]
+    assert_min_max!(i128, u128);
+
+    assert_eq!(NonZeroU64::MIN_STR, "1");
+    assert_eq!(NonZeroU64::MAX_STR, u64::MAX_STR);
+    assert_eq!(NonZeroI32::MIN_STR, "-2147483648");
+    assert_eq!(<Wrapping<u8>>::MIN_STR, "0");
+    assert_eq!(<&i8>::MAX_STR, "127");
+}
+
+#[test]
+fn test_unsigned_abs() {
+    assert_eq!(itoa::unsigned_abs(<i8>::min_value()), 128u8);
+    assert_eq!(itoa::unsigned_abs(<i16>::min_value()), 32768u16);
+    assert_eq!(itoa::unsigned_abs(<i32>::min_value()), 2147483648u32);
+    assert_eq!(
+        itoa::unsigned_abs(<i64>::min_value()),
+        9223372036854775808u64
+    );
+    assert_eq!(
+        itoa::unsigned_abs(<isize>::min_value()),
+        <isize>::max_value() as usize + 1
+    );
+    assert_eq!(itoa::unsigned_abs(-1i32), 1u32);
+    assert_eq!(itoa::unsigned_abs(<i32>::max_value()), 2147483647u32);
+
+    for &n in &[0u8, 1, <u8>::max_value()] {
+        assert_eq!(itoa::unsigned_abs(n), n);
+    }
+    assert_eq!(itoa::unsigned_abs(<u64>::max_value()), <u64>::max_value());
+    assert_eq!(
+        itoa::unsigned_abs(<usize>::max_value()),
+        <usize>::max_value()
+    );
+
+    let n = std::num::NonZeroI16::new(-300).unwrap();
+    assert_eq!(itoa::unsigned_abs(n), 300u16);
+    assert_eq!(itoa::unsigned_abs(std::num::Wrapping(-5i8)), 5u8);
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_unsigned_abs_i128() {
+    assert_eq!(
+        itoa::unsigned_abs(<i128>::min_value()),
+        <i128>::max_value() as u128 + 1
+    );
+    assert_eq!(itoa::unsigned_abs(<u128>::max_value()), <u128>::max_value());
+}
+
+#[test]
+fn test_format_as() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(itoa::format_as::<u32, i64>(&mut buffer, 0), Some("0"));
+    assert_eq!(
+        itoa::format_as::<u32, i64>(&mut buffer, 4294967295),
+        Some("4294967295")
+    );
+    assert_eq!(itoa::format_as::<u32, i64>(&mut buffer, 4294967296), None);
+    assert_eq!(itoa::format_as::<u32, i64>(&mut buffer, -1), None);
+    assert_eq!(itoa::format_as::<i8, u64>(&mut buffer, 127), Some("127"));
+    assert_eq!(itoa::format_as::<i8, u64>(&mut buffer, 128), None);
+    assert_eq!(itoa::format_as::<i64, u8>(&mut buffer, 255), Some("255"));
+}
+
+#[cfg(feature = "ffi")]
+#[test]
+fn test_ffi_format_u64() {
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::ptr;
+
+    let mut buf = itoa::FfiBuffer::new();
+    let mut len = 0;
+    for &n in &[0u64, 42, <u64>::max_value()] {
+        let ptr = unsafe { itoa::itoa_format_u64(&mut buf, n, &mut len) };
+        let s = unsafe { CStr::from_ptr(ptr as *const c_char) };
+        assert_eq!(s.to_str(), Ok(&*n.to_string()));
+        assert_eq!(len, s.to_bytes().len());
+    }
+
+    let ptr = unsafe { itoa::itoa_format_u64(&mut buf, 7, ptr::null_mut()) };
+    assert_eq!(
+        unsafe { CStr::from_ptr(ptr as *const c_char) }.to_bytes(),
+        b"7"
+    );
+
+    len = 99;
+    let ptr = unsafe { itoa::itoa_format_u64(ptr::null_mut(), 7, &mut len) };
+    assert!(ptr.is_null());
+    assert_eq!(len, 0);
+}
+
+#[test]
+fn test_format_narrowest() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(itoa::format_narrowest(&mut buffer, 0), ("0", 1));
+    assert_eq!(itoa::format_narrowest(&mut buffer, 255), ("255", 1));
+    assert_eq!(itoa::format_narrowest(&mut buffer, 256), ("256", 2));
+    assert_eq!(itoa::format_narrowest(&mut buffer, 65535), ("65535", 2));
+    assert_eq!(itoa::format_narrowest(&mut buffer, 65536), ("65536", 4));
+    let max = <u32>::max_value() as u64;
+    assert_eq!(itoa::format_narrowest(&mut buffer, max), ("4294967295", 4));
+    assert_eq!(
+        itoa::format_narrowest(&mut buffer, max + 1),
+        ("4294967296", 8)
+    );
+    assert_eq!(
+        itoa::format_narrowest(&mut buffer, <u64>::max_value()),
+        ("18446744073709551615", 8)
+    );
+}
+
+#[test]
+fn test_write_uninit() {
+    use itoa::Integer;
+    use std::mem::MaybeUninit;
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 32];
+    assert_eq!(itoa::write_uninit(&mut buf, 0u8), b"0");
+    assert_eq!(
+        itoa::write_uninit(&mut buf, <i64>::min_value()),
+        b"-9223372036854775808"
+    );
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); u64::MAX_STR_LEN];
+    assert_eq!(
+        itoa::write_uninit(&mut buf, <u64>::max_value()),
+        b"18446744073709551615"
+    );
+    let mut buf = [MaybeUninit::<u8>::uninit(); i16::MAX_STR_LEN];
+    assert_eq!(itoa::write_uninit(&mut buf, -300i16), b"-300");
+}
+
+#[test]
+fn test_format_uninit() {
+    use itoa::Integer;
+    use std::mem::MaybeUninit;
+
+    let mut exact = [MaybeUninit::<u8>::uninit(); i32::MAX_STR_LEN];
+    assert_eq!(
+        itoa::format_uninit(&mut exact, <i32>::min_value()),
+        Some("-2147483648")
+    );
+    assert_eq!(itoa::format_uninit(&mut exact, 0i32), Some("0"));
+
+    let mut small = [MaybeUninit::<u8>::uninit(); 10];
+    assert_eq!(itoa::format_uninit(&mut small, 5i32), None);
+    assert_eq!(itoa::format_uninit(&mut small, 5u32), Some("5"));
+    assert_eq!(itoa::format_uninit(&mut [], 0u8), None);
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_write_uninit_i128() {
+    use itoa::Integer;
+    use std::mem::MaybeUninit;
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); i128::MAX_STR_LEN];
+    assert_eq!(
+        itoa::write_uninit(&mut buf, <i128>::min_value()),
+        &b"-170141183460469231731687303715884105728"[..]
+    );
+
+    let mut raw: itoa::RawBuffer = [MaybeUninit::uninit(); itoa::MAX_LEN];
+    assert_eq!(i128::MAX_STR_LEN, itoa::MAX_LEN);
+    assert_eq!(
+        itoa::write_uninit(&mut raw, <i128>::min_value()),
+        &b"-170141183460469231731687303715884105728"[..]
+    );
+    assert_eq!(
+        itoa::format_uninit(&mut raw, <u128>::max_value()),
+        Some("340282366920938463463374607431768211455")
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_write_uninit_too_short() {
+    let mut buf = [std::mem::MaybeUninit::<u8>::uninit(); 19];
+    itoa::write_uninit(&mut buf, 1u64);
+}
+
+// Every pair of the two-digit lookup table, through each code path that reads
+// from it.
+#[test]
+fn test_two_digit_pairs() {
+    let mut buffer = itoa::Buffer::new();
+    let mut dst = [0u8; 4];
+    for n in 0..100u32 {
+        let expected = format!("{:02}", n);
+        assert_eq!(buffer.format_padded(n, 2), expected);
+        assert_eq!(buffer.format(n + 100)[1..], expected);
+        assert_eq!(buffer.format(n * 100 + 10000)[1..3], expected);
+        let len = itoa::write_forward(&mut dst, n + 100).unwrap();
+        assert_eq!(&dst[1..len], expected.as_bytes());
+    }
+}
+
+// Under debug assertions every write also checks its own offsets and length.
+#[test]
+fn test_exhaustive_16_bit() {
+    let mut buffer = itoa::Buffer::new();
+    let mut typed = itoa::TypedBuffer::<i16>::new();
+    for n in <i16>::min_value()..=<i16>::max_value() {
+        let expected = n.to_string();
+        assert_eq!(buffer.format(n), expected);
+        assert_eq!(typed.format(n), expected);
+    }
+    for n in <u16>::min_value()..=<u16>::max_value() {
+        assert_eq!(buffer.format(n), n.to_string());
+    }
+}
+
+#[test]
+fn test_written_len() {
+    let mut buffer = itoa::Buffer::new();
+    let mut n = 1u64;
+    loop {
+        for &n in &[n - 1, n, n + 1] {
+            assert_eq!(itoa::written_len(n), buffer.format(n).len());
+            let n = n as i64;
+            assert_eq!(itoa::written_len(n), buffer.format(n).len());
+            assert_eq!(itoa::written_len(-n), buffer.format(-n).len());
+        }
+        n = match n.checked_mul(10) {
+            Some(n) => n,
+            None => break,
+        };
+    }
+    for &n in &[<u64>::max_value(), <u64>::max_value() - 1] {
+        assert_eq!(itoa::written_len(n), buffer.format(n).len());
+    }
+    for &n in &[<i8>::min_value(), <i8>::max_value(), 0, -1, 99, -100] {
+        assert_eq!(itoa::written_len(n), buffer.format(n).len());
+    }
+    assert_eq!(itoa::written_len(<i64>::min_value()), 20);
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_written_len_i128() {
+    let mut buffer = itoa::Buffer::new();
+    let mut n = 1u128;
+    while let Some(next) = n.checked_mul(10) {
+        for &n in &[n - 1, n, n + 1] {
+            assert_eq!(itoa::written_len(n), buffer.format(n).len());
+        }
+        n = next;
+    }
+    for &n in &[<u128>::max_value(), 0] {
+        assert_eq!(itoa::written_len(n), buffer.format(n).len());
+    }
+    for &n in &[<i128>::min_value(), <i128>::max_value(), -1] {
+        assert_eq!(itoa::written_len(n), buffer.format(n).len());
+    }
+}
+
+// xorshift64*, so the sample is the same on every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    // Random bits shifted down by a random amount, to cover every length.
+    fn sample(&mut self) -> u64 {
+        let bits = self.next();
+        bits >> (self.next() % 64)
+    }
+
+    // Same as `sample` for 128 bits, which truncates to any narrower type.
+    fn wide(&mut self) -> u128 {
+        let bits = (self.next() as u128) << 64 | self.next() as u128;
+        bits >> (self.next() % 128)
+    }
+}
+
+// Checks `$check` against the boundaries of every integer type and a random
+// sample of its values. Each type gets its own stream seeded with `$seed`.
+macro_rules! fuzz {
+    ($($name:ident($seed:expr, |$buffer:ident: $buf:ty, $n:ident| $check:expr)),*) => {$(
+        #[test]
+        fn $name() {
+            fuzz!(@types $seed, |$buffer: $buf, $n| $check, i8 u8 i16 u16 i32 u32 i64 u64 isize usize);
+            #[cfg(feature = "i128")]
+            fuzz!(@types $seed, |$buffer: $buf, $n| $check, i128 u128);
+        }
+    )*};
+    (@types $seed:expr, |$buffer:ident: $buf:ty, $n:ident| $check:expr, $($t:ident)*) => {$({
+        let mut rng = Rng($seed);
+        let mut $buffer = <$buf>::new();
+        let boundaries: [$t; 6] = [
+            <$t>::min_value(),
+            <$t>::min_value() + 1,
+            0,
+            1,
+            <$t>::max_value() - 1,
+            <$t>::max_value(),
+        ];
+        for &$n in &boundaries {
+            $check;
+        }
+        for _ in 0..10_000 {
+            let $n = rng.wide() as $t;
+            $check;
+        }
+    })*};
+}
+
+fn max_str_of<I: itoa::Integer>(_: I) -> &'static str {
+    I::MAX_STR
+}
+
+fuzz! {
+    fuzz_format(0x9e37_79b9_7f4a_7c15, |buffer: itoa::Buffer, n| {
+        let expected = format!("{}", n);
+        assert_eq!(buffer.format(n), expected);
+        let mut s = String::new();
+        itoa::fmt(&mut s, n).unwrap();
+        assert_eq!(s, expected);
+    }),
+    fuzz_binary(0x94d0_49bb_1331_11eb, |buffer: itoa::WideBuffer, n| {
+        assert_eq!(buffer.format_binary(n), format!("{:b}", n));
+    }),
+    fuzz_octal(0x2545_f491_4f6c_dd1d, |buffer: itoa::WideBuffer, n| {
+        assert_eq!(buffer.format_octal(n), format!("{:o}", n));
+    }),
+    fuzz_hex_prefixed(0xd6e8_feb8_6659_fd93, |buffer: itoa::WideBuffer, n| {
+        assert_eq!(buffer.format_hex_prefixed(n), format!("{:#x}", n));
+    }),
+    fuzz_radix_16(0x1b87_3593_cc9e_2d51, |buffer: itoa::WideBuffer, n| {
+        let s = buffer.format_radix(n, 16);
+        let digits = s.trim_start_matches('-');
+        let magnitude = u128::from_str_radix(digits, 16).unwrap();
+        let sign = if digits.len() < s.len() { "-" } else { "" };
+        assert_eq!(format!("{}{}", sign, magnitude), n.to_string());
+        assert_eq!(digits, format!("{:x}", magnitude));
+    }),
+    fuzz_full_width(0x3c6e_f372_fe94_f82b, |buffer: itoa::Buffer, n| {
+        let s = buffer.format_full_width(n);
+        assert_eq!(s.trim_start_matches('-').len(), max_str_of(n).len());
+        assert_eq!(s.parse(), Ok(n));
+    }),
+    // Every API that takes a value, with degenerate widths and sizes, to check
+    // that none of them panic. Widths saturate at the buffer capacity.
+    fuzz_no_panic(0x7f4a_7c15_9e37_79b9, |buffer: itoa::Buffer, n| {
+        let max = <usize>::max_value();
+        let mut wide = itoa::WideBuffer::new();
+        wide.format_binary_prefixed(n);
+        wide.format_octal_prefixed(n);
+        wide.format_hex_padded(n);
+        wide.format_radix(n, 2);
+        wide.format_radix(n, 36);
+        wide.format_base36_upper(n);
+        assert_eq!(buffer.format_padded(n, max).len(), 40);
+        buffer.format_padded(n, 0);
+        assert_eq!(buffer.format_right_aligned(n, max, b' ').len(), 40);
+        assert_eq!(buffer.format_field(n, max).len(), 40);
+        assert_eq!(buffer.format_field(n, 0), "");
+        wide.format_fixed_point(n, 0);
+        wide.format_fixed_point(n, max);
+        buffer.format_signed(n);
+        buffer.format_abs(n);
+        buffer.format_reversed(n);
+        buffer.format_with_sign(n, b'~', Some(b'+'));
+        buffer.format_padded_constant_time(n);
+        wide.format_accounting(n);
+        buffer.format_compact(n);
+        wide.format_grouped(n, b',', 1);
+        wide.format_grouped(n, b',', max);
+        wide.format_grouped_indian(n, b',');
+        buffer.format_detailed(n);
+        while buffer.format_appending(n).is_some() {}
+        buffer.reset();
+
+        let mut formatted = itoa::to_array(n);
+        assert!(formatted.pad_left(max, b' ').len() <= 64);
+        assert!(formatted.pad_right(max, b' ').len() <= 64);
+        assert_eq!(itoa::digits(n).len(), itoa::written_len(n));
+        itoa::unsigned_digits(n);
+        itoa::unsigned_abs(n);
+        assert_eq!(itoa::format_into(&mut [], n), Err(()));
+        assert_eq!(itoa::write_forward(&mut [0u8; 1][..], n).is_some(), itoa::written_len(n) == 1);
+        let mut raw: itoa::RawBuffer = [std::mem::MaybeUninit::uninit(); itoa::MAX_LEN];
+        itoa::write_uninit(&mut raw, n);
+        assert_eq!(itoa::format_uninit(&mut raw[..1], n), None);
+        let mut s = String::new();
+        itoa::fmt_grouped(&mut s, n, "", max).unwrap();
+        itoa::format_with_digits(&mut s, n, &["0"; 10], "").unwrap();
+    })
+}
+
+#[test]
+fn test_random_u64() {
+    let mut rng = Rng(0x853c_49e6_748f_ea9b);
+    let mut buffer = itoa::Buffer::new();
+    for _ in 0..200_000 {
+        let n = rng.sample();
+        assert_eq!(buffer.format(n), n.to_string());
+        let n = n as i64;
+        assert_eq!(buffer.format(n), n.to_string());
+    }
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_random_u128() {
+    let mut rng = Rng(0xda3e_39cb_94b9_5bdb);
+    let mut buffer = itoa::Buffer::new();
+    for _ in 0..100_000 {
+        let n = (rng.sample() as u128) << 64 | rng.next() as u128;
+        let n = n >> (rng.next() % 128);
+        assert_eq!(buffer.format(n), n.to_string());
+        let n = n as i128;
+        assert_eq!(buffer.format(n), n.to_string());
+    }
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_u128_chunk_boundaries() {
+    let e19 = 10_000_000_000_000_000_000u128;
+    let e38 = e19 * e19;
+    let mut buffer = itoa::Buffer::new();
+    for &base in &[e19, <u64>::max_value() as u128 + 1, e38] {
+        for &n in &[base - 1, base, base + 1] {
+            assert_eq!(buffer.format(n), n.to_string());
+            assert_eq!(buffer.format(n as i128), (n as i128).to_string());
+            assert_eq!(buffer.format(-(n as i128)), (-(n as i128)).to_string());
+        }
+    }
+    assert_eq!(buffer.format(e19), "10000000000000000000");
+    assert_eq!(
+        buffer.format(e38),
+        "100000000000000000000000000000000000000",
+    );
+}
+
+#[cfg(feature = "safe")]
+#[test]
+fn test_safe_boundaries() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format(0u8), "0");
+    assert_eq!(buffer.format(9u8), "9");
+    assert_eq!(buffer.format(10u8), "10");
+    assert_eq!(buffer.format(<i8>::min_value()), "-128");
+    assert_eq!(buffer.format(<u16>::max_value()), "65535");
+    assert_eq!(buffer.format(9999i32), "9999");
+    assert_eq!(buffer.format(10000i32), "10000");
+    assert_eq!(buffer.format(<i32>::min_value()), "-2147483648");
+    assert_eq!(buffer.format(<u32>::max_value()), "4294967295");
+    assert_eq!(buffer.format(<i64>::min_value()), "-9223372036854775808");
+    assert_eq!(buffer.format(<u64>::max_value()), "18446744073709551615");
+    assert_eq!(
+        buffer.format(<isize>::min_value()),
+        <isize>::min_value().to_string()
+    );
+    assert_eq!(
+        buffer.format(<usize>::max_value()),
+        <usize>::max_value().to_string()
+    );
+}
+
+#[cfg(all(feature = "safe", feature = "i128"))]
+#[test]
+fn test_safe_boundaries_i128() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(
+        buffer.format(<u64>::max_value() as u128 + 1),
+        "18446744073709551616"
+    );
+    assert_eq!(
+        buffer.format(<i128>::min_value()),
+        "-170141183460469231731687303715884105728"
+    );
+    assert_eq!(
+        buffer.format(<u128>::max_value()),
+        "340282366920938463463374607431768211455"
+    );
+}
+
+#[test]
+fn test_format_into_truncating() {
+    let mut dst = [0u8; 20];
+    assert_eq!(
+        itoa::format_into_truncating(&mut dst, <u64>::max_value()),
+        "18446744073709551615"
+    );
+    assert_eq!(itoa::format_into_truncating(&mut dst, 0), "0");
+
+    let mut dst = [0u8; 5];
+    assert_eq!(itoa::format_into_truncating(&mut dst, 12345), "12345");
+    assert_eq!(itoa::format_into_truncating(&mut dst, 123456), "1234*");
+
+    let mut dst = [0u8; 1];
+    assert_eq!(itoa::format_into_truncating(&mut dst, 7), "7");
+    assert_eq!(itoa::format_into_truncating(&mut dst, 10), "*");
+
+    assert_eq!(itoa::format_into_truncating(&mut [], 1), "");
+}
+
+#[test]
+fn test_fmt_bounded() {
+    let mut s = String::new();
+    assert_eq!(itoa::fmt_bounded(&mut s, <i64>::min_value(), 20), Ok(20));
+    assert_eq!(s, "-9223372036854775808");
+
+    let mut s = String::new();
+    assert_eq!(itoa::fmt_bounded(&mut s, 12345u32, 5), Ok(5));
+    assert_eq!(s, "12345");
+
+    let mut s = String::new();
+    assert_eq!(itoa::fmt_bounded(&mut s, 123456u32, 5), Ok(5));
+    assert_eq!(s, "12345");
+
+    let mut s = String::new();
+    assert_eq!(itoa::fmt_bounded(&mut s, -42i8, 1), Ok(1));
+    assert_eq!(itoa::fmt_bounded(&mut s, 42u8, 0), Ok(0));
+    assert_eq!(s, "-");
+}
+
+#[test]
+fn test_format_fields() {
+    let mut s = String::new();
+    let fields = [
+        (42, 4),
+        (12345678, 8),
+        (-7, 3),
+        (0, 1),
+        (<i64>::min_value(), 20),
+    ];
+    itoa::format_fields(&mut s, &fields).unwrap();
+    assert_eq!(s, "004212345678-070-9223372036854775808");
+    assert_eq!(s.len(), 4 + 8 + 3 + 1 + 20);
+
+    let mut s = String::from("keep");
+    assert!(itoa::format_fields(&mut s, &[(1, 2), (-100, 3)]).is_err());
+    assert_eq!(s, "keep");
+    itoa::format_fields(&mut s, &[(-1, 130)]).unwrap();
+    assert_eq!(s.len(), 4 + 130);
+    assert!(s.starts_with("keep-000"));
+    assert!(s.ends_with("0001"));
+
+    let mut s = String::new();
+    itoa::format_fields(&mut s, &[]).unwrap();
+    assert_eq!(s, "");
+}
+
+#[test]
+fn test_write_forward() {
+    let mut buffer = itoa::Buffer::new();
+    let mut dst = [0u8; 20];
+    let mut rng = Rng(0x6b8f_0e43_a17c_2d95);
+    for _ in 0..10_000 {
+        let n = rng.next() >> (rng.next() % 64);
+        let len = itoa::write_forward(&mut dst, n).unwrap();
+        assert_eq!(&dst[..len], buffer.format(n).as_bytes());
+        let n = n as i64;
+        let len = itoa::write_forward(&mut dst, n).unwrap();
+        assert_eq!(&dst[..len], buffer.format(n).as_bytes());
+        let n = n as i16;
+        let len = itoa::write_forward(&mut dst, n).unwrap();
+        assert_eq!(&dst[..len], buffer.format(n).as_bytes());
+    }
+
+    let len = itoa::write_forward(&mut dst, <i64>::min_value()).unwrap();
+    assert_eq!(&dst[..len], b"-9223372036854775808");
+
+    let mut dst = [b'.'; 3];
+    assert_eq!(itoa::write_forward(&mut dst, -100i32), None);
+    assert_eq!(&dst, b"...");
+    assert_eq!(itoa::write_forward(&mut dst, -10i32), Some(3));
+    assert_eq!(&dst, b"-10");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_write_forward_i128() {
+    let mut buffer = itoa::Buffer::new();
+    let mut dst = [0u8; 40];
+    let mut rng = Rng(0x2c41_9d7e_f063_b58a);
+    for _ in 0..1000 {
+        let n = ((rng.next() as u128) << 64 | rng.next() as u128) >> (rng.next() % 128);
+        let len = itoa::write_forward(&mut dst, n).unwrap();
+        assert_eq!(&dst[..len], buffer.format(n).as_bytes());
+        let n = n as i128;
+        let len = itoa::write_forward(&mut dst, n).unwrap();
+        assert_eq!(&dst[..len], buffer.format(n).as_bytes());
+    }
+    let len = itoa::write_forward(&mut dst, <i128>::min_value()).unwrap();
+    assert_eq!(&dst[..len], buffer.format(<i128>::min_value()).as_bytes());
+}
+
+#[test]
+fn test_format_split() {
+    let mut first = [b'.'; 8];
+    let mut second = [b'.'; 8];
+    assert_eq!(itoa::format_split(&mut first, &mut second, 12345), Some(5));
+    assert_eq!(&first, b"12345...");
+    assert_eq!(&second, b"........");
+
+    let mut first = [b'.'; 4];
+    let mut second = [b'.'; 8];
+    assert_eq!(itoa::format_split(&mut first, &mut second, 123456), Some(6));
+    assert_eq!(&first, b"1234");
+    assert_eq!(&second, b"56......");
+
+    let mut first = [b'.'; 4];
+    let mut second = [b'.'; 16];
+    assert_eq!(
+        itoa::format_split(&mut first, &mut second, <u64>::max_value()),
+        Some(20)
+    );
+    assert_eq!(&first, b"1844");
+    assert_eq!(&second, b"6744073709551615");
+
+    assert_eq!(itoa::format_split(&mut [], &mut [b'.'; 1], 7), Some(1));
+
+    let mut first = [b'.'; 2];
+    let mut second = [b'.'; 3];
+    assert_eq!(itoa::format_split(&mut first, &mut second, 123456), None);
+    assert_eq!(&first, b"..");
+    assert_eq!(&second, b"...");
+}
+
+#[test]
+fn test_digits() {
+    let mut buffer = itoa::Buffer::new();
+    for &n in &[
+        0i64,
+        7,
+        -7,
+        1234567890,
+        <i64>::min_value(),
+        <i64>::max_value(),
+    ] {
+        let digits = itoa::digits(n);
+        assert_eq!(digits.len(), buffer.format(n).len());
+        assert_eq!(digits.collect::<Vec<u8>>(), buffer.format(n).as_bytes());
+    }
+    assert_eq!(itoa::digits(<u8>::max_value()).collect::<Vec<u8>>(), b"255");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_digits_i128() {
+    let mut buffer = itoa::Buffer::new();
+    for &n in &[<i128>::min_value(), <i128>::max_value(), -1] {
+        let digits: Vec<u8> = itoa::digits(n).collect();
+        assert_eq!(digits, buffer.format(n).as_bytes());
+    }
+    let digits: Vec<u8> = itoa::digits(<u128>::max_value()).collect();
+    assert_eq!(digits, buffer.format(<u128>::max_value()).as_bytes());
+}
+
+#[test]
+fn test_unsigned_digits() {
+    let digits: Vec<u8> = itoa::unsigned_digits(-123i32).collect();
+    assert_eq!(digits, [b'1', b'2', b'3']);
+    assert_eq!(itoa::unsigned_digits(123u8).collect::<Vec<u8>>(), b"123");
+    assert_eq!(itoa::unsigned_digits(0i8).collect::<Vec<u8>>(), b"0");
+    let digits = itoa::unsigned_digits(<i64>::min_value());
+    assert_eq!(digits.len(), 19);
+    assert_eq!(digits.collect::<Vec<u8>>(), b"9223372036854775808");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_unsigned_digits_i128() {
+    let digits: Vec<u8> = itoa::unsigned_digits(<i128>::min_value()).collect();
+    assert_eq!(digits, &b"170141183460469231731687303715884105728"[..]);
+}
+
+#[test]
+fn test_to_array() {
+    let mut buffer = itoa::Buffer::new();
+    for &n in &[
+        0i64,
+        7,
+        -7,
+        1234567890,
+        <i64>::min_value(),
+        <i64>::max_value(),
+    ] {
+        let formatted = itoa::to_array(n);
+        assert_eq!(formatted.as_str(), buffer.format(n));
+        assert_eq!(formatted.as_bytes(), buffer.format(n).as_bytes());
+    }
+    let copy = itoa::to_array(<u8>::max_value());
+    let moved = copy;
+    assert_eq!(copy.as_str(), "255");
+    assert_eq!(moved.as_str(), "255");
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_to_array_i128() {
+    let n = <i128>::min_value();
+    assert_eq!(itoa::to_array(n).as_str(), n.to_string());
+}
+
+#[test]
+fn test_formatted_padding() {
+    let mut formatted = itoa::to_array(-42i32);
+    assert_eq!(formatted.pad_left(6, b' '), "   -42");
+    assert_eq!(formatted.pad_right(6, b' '), "-42   ");
+    assert_eq!(formatted.pad_left(4, b'*'), "*-42");
+    assert_eq!(formatted.pad_right(2, b'*'), "-42");
+    assert_eq!(formatted.pad_left(1000, b' ').len(), 64);
+    assert_eq!(formatted.pad_right(1000, b' ').len(), 64);
+    assert_eq!(formatted.as_str(), "-42");
+
+    let mut formatted = itoa::to_array(<u64>::max_value());
+    let right = formatted.pad_right(64, b'.').to_owned();
+    assert!(right.starts_with("18446744073709551615."));
+    assert!(formatted
+        .pad_left(64, b'.')
+        .ends_with(".18446744073709551615"));
+}
+
+#[test]
+fn test_cached() {
+    let mut buffer = itoa::Buffer::new();
+    for &n in &[0i64, -7, 1234567890, <i64>::min_value(), <i64>::max_value()] {
+        let cached = itoa::Cached::new(n);
+        let first = cached.to_string();
+        assert_eq!(cached.to_string(), first);
+        assert_eq!(cached.to_string(), first);
+        assert_eq!(cached.as_ref(), buffer.format(n));
+        assert_eq!(*cached.get(), n);
+    }
+    let cached = itoa::Cached::new(<u8>::max_value());
+    assert_eq!(format!("{:<5}|{:?}", cached, cached), "255  |255");
+}
+
+#[test]
+fn test_to_bcd() {
+    let mut dst = [0xffu8; 10];
+    assert_eq!(itoa::to_bcd(0, &mut dst), Some(1));
+    assert_eq!(dst[0], 0x00);
+    assert_eq!(itoa::to_bcd(1234, &mut dst), Some(2));
+    assert_eq!(&dst[..2], &[0x12, 0x34]);
+    assert_eq!(itoa::to_bcd(12345, &mut dst), Some(3));
+    assert_eq!(&dst[..3], &[0x01, 0x23, 0x45]);
+    assert_eq!(itoa::to_bcd(<u64>::max_value(), &mut dst), Some(10));
+    assert_eq!(
+        dst,
+        [0x18, 0x44, 0x67, 0x44, 0x07, 0x37, 0x09, 0x55, 0x16, 0x15]
+    );
+
+    let mut short = [0xffu8; 2];
+    assert_eq!(itoa::to_bcd(12345, &mut short), None);
+    assert_eq!(short, [0xff, 0xff]);
+}
+
+#[derive(Copy, Clone)]
+struct U256([u64; 4]);
+
+impl itoa::DivMod1e19 for U256 {
+    fn divmod_1e19(self) -> (Self, u64) {
+        let d = 10_000_000_000_000_000_000_u64;
+        let mut quotient = [0u64; 4];
+        let mut rem = 0u64;
+        for i in (0..4).rev() {
+            let n = (rem as u128) << 64 | self.0[i] as u128;
+            quotient[i] = (n / d as u128) as u64;
+            rem = (n % d as u128) as u64;
+        }
+        (U256(quotient), rem)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+}
+
+#[test]
+fn test_fmt_chunked() {
+    let mut s = String::new();
+    for &n in &[0u64, 1, 9_999_999_999_999_999_999, <u64>::max_value()] {
+        s.clear();
+        itoa::fmt_chunked(&mut s, n).unwrap();
+        assert_eq!(s, n.to_string());
+    }
+
+    let cases: &[([u64; 4], &str)] = &[
+        ([0, 0, 0, 0], "0"),
+        ([0, 1, 0, 0], "18446744073709551616"),
+        ([0, 0, 1, 0], "340282366920938463463374607431768211456"),
+        (
+            [<u64>::max_value(); 4],
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+        ),
+    ];
+    for &(limbs, expected) in cases {
+        s.clear();
+        itoa::fmt_chunked(&mut s, U256(limbs)).unwrap();
+        assert_eq!(s, expected);
+    }
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_fmt_chunked_u128() {
+    let mut s = String::new();
+    for &n in &[
+        0u128,
+        1 << 64,
+        10_000_000_000_000_000_000 << 64,
+        <u128>::max_value(),
+    ] {
+        s.clear();
+        itoa::fmt_chunked(&mut s, n).unwrap();
+        assert_eq!(s, n.to_string());
+    }
+}
+
+#[cfg(feature = "ethnum")]
+#[test]
+fn test_fmt_chunked_ethnum() {
+    use ethnum::{I256, U256};
+
+    let mut s = String::new();
+    for &n in &[
+        U256::ZERO,
+        U256::ONE,
+        U256::new(10_000_000_000_000_000_000),
+        U256::from_words(1, 0),
+        U256::from_words(10_000_000_000_000_000_000, 12345),
+        U256::MAX - 1,
+        U256::MAX,
+    ] {
+        s.clear();
+        itoa::fmt_chunked(&mut s, n).unwrap();
+        assert_eq!(s, n.to_string());
+    }
+    for &n in &[
+        I256::MIN,
+        I256::MIN + 1,
+        I256::new(-10_000_000_000_000_000_000),
+        I256::MINUS_ONE,
+        I256::ZERO,
+        I256::ONE,
+        I256::from_words(1, 0),
+        -I256::from_words(1, 0),
+        I256::MAX,
+    ] {
+        s.clear();
+        itoa::fmt_chunked(&mut s, n).unwrap();
+        assert_eq!(s, n.to_string());
+    }
+}
+
+#[test]
+fn test_digit_sum() {
+    assert_eq!(itoa::digit_sum(12345), 15);
+    assert_eq!(itoa::digit_sum(0u8), 0);
+    assert_eq!(itoa::digit_sum(-12345i32), 15);
+    assert_eq!(itoa::digit_sum(<i8>::min_value()), 11);
+    assert_eq!(itoa::digit_sum(<u64>::max_value()), 87);
+}
+
+#[test]
+fn test_luhn_check_digit() {
+    assert_eq!(itoa::luhn_check_digit(7992739871), 3);
+    // Visa test card 4111 1111 1111 1111.
+    assert_eq!(itoa::luhn_check_digit(411111111111111), 1);
+    assert_eq!(itoa::luhn_check_digit(0), 0);
+    assert_eq!(itoa::luhn_check_digit(5), 9);
+}
+
+#[cfg(not(no_const_generics))]
+#[test]
+fn test_to_padded_array() {
+    assert_eq!(itoa::to_padded_array::<4>(1234), Some(*b"1234"));
+    assert_eq!(itoa::to_padded_array::<8>(1234), Some(*b"    1234"));
+    assert_eq!(itoa::to_padded_array::<3>(1234), None);
+    assert_eq!(itoa::to_padded_array::<1>(0), Some(*b"0"));
+    assert_eq!(itoa::to_padded_array::<0>(0), None);
+    assert_eq!(
+        itoa::to_padded_array::<20>(<u64>::max_value()),
+        Some(*b"18446744073709551615")
+    );
+    assert_eq!(itoa::to_padded_array::<19>(<u64>::max_value()), None);
+}
+
+#[test]
+fn test_format_ebcdic() {
+    let mut dst = [0u8; 20];
+    assert_eq!(itoa::format_ebcdic(1234567890, &mut dst), Some(10));
+    assert_eq!(
+        &dst[..10],
+        &[0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9, 0xF0]
+    );
+    assert_eq!(itoa::format_ebcdic(-7, &mut dst), Some(2));
+    assert_eq!(&dst[..2], &[0x60, 0xF7]);
+    assert_eq!(itoa::format_ebcdic(<i64>::min_value(), &mut dst), Some(20));
+    assert_eq!(dst[0], 0x60);
+    assert!(dst[1..].iter().all(|b| (0xF0..=0xF9).contains(b)));
+
+    let mut short = [0xAAu8; 2];
+    assert_eq!(itoa::format_ebcdic(-42, &mut short), None);
+    assert_eq!(short, [0xAA, 0xAA]);
+}
+
+#[test]
+fn test_format_into() {
+    let mut exact = [0u8; 4];
+    assert_eq!(itoa::format_into(&mut exact, -128i8), Ok("-128"));
+
+    let mut short = [b'x'; 3];
+    assert_eq!(itoa::format_into(&mut short, -128i8), Err(()));
+    assert_eq!(short, *b"xxx");
+
+    let mut oversized = [b'x'; 8];
+    assert_eq!(itoa::format_into(&mut oversized, 42u64), Ok("42"));
+    assert_eq!(oversized, *b"42xxxxxx");
+
+    assert_eq!(itoa::format_into(&mut [], 0u8), Err(()));
+}
+
+#[test]
+fn test_display() {
+    use itoa::Itoa;
+
+    assert_eq!(Itoa(42u8).to_string(), "42");
+    assert_eq!(Itoa(<i64>::min_value()).to_string(), "-9223372036854775808");
+    assert_eq!(format!("{:>8}", Itoa(42)), "      42");
+    assert_eq!(format!("{:<8}|", Itoa(-42)), "-42     |");
+    assert_eq!(format!("{:*^7}", Itoa(42)), "**42***");
+    assert_eq!(format!("{:+}", Itoa(42)), "+42");
+    assert_eq!(format!("{:05}", Itoa(-42)), "-0042");
+    assert_eq!(format!("{:+05}", Itoa(42)), "+0042");
+    for &n in &[0i32, 7, -7, <i32>::max_value(), <i32>::min_value()] {
+        assert_eq!(format!("{:>+12}", Itoa(n)), format!("{:>+12}", n));
+        assert_eq!(format!("{:012}", Itoa(n)), format!("{:012}", n));
+    }
+}
+
+#[test]
+fn test_to_str() {
+    use itoa::ToStr;
+
+    fn both<T: ToStr>(a: T, b: T) -> String {
+        let mut buf = itoa::Buffer::new();
+        let mut s = String::from(a.itoa_to_str(&mut buf));
+        s.push(' ');
+        s.push_str(b.itoa_to_str(&mut buf));
+        s
+    }
+
+    assert_eq!(both(<i64>::min_value(), 0), "-9223372036854775808 0");
+    assert_eq!(
+        both(std::num::Wrapping(255u8), std::num::Wrapping(1)),
+        "255 1"
+    );
+    let n = 12u16;
+    assert_eq!(both(&n, &n), "12 12");
+}
+
+#[test]
+fn test_debug_alternate() {
+    use itoa::Itoa;
+
+    assert_eq!(format!("{:?}", Itoa(1234567)), "1234567");
+    assert_eq!(format!("{:>6?}", Itoa(-42)), "   -42");
+    assert_eq!(format!("{:#?}", Itoa(1234567)), "1,234,567");
+    assert_eq!(format!("{:#?}", Itoa(-1234567i64)), "-1,234,567");
+    assert_eq!(format!("{:#?}", Itoa(999u16)), "999");
+    assert_eq!(format!("{:#?}", Some(Itoa(1000))), "Some(\n    1,000,\n)");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_as_str() {
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Record {
+        #[serde(serialize_with = "itoa::serde::as_str")]
+        id: u64,
+        #[serde(serialize_with = "itoa::serde::as_str")]
+        delta: i32,
+        count: u32,
+    }
+
+    let record = Record {
+        id: <u64>::max_value(),
+        delta: -5,
+        count: 3,
+    };
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(
+        json,
+        r#"{"id":"18446744073709551615","delta":"-5","count":3}"#
+    );
+}
+
+#[test]
+fn test_fmt_grouped() {
+    let mut s = String::new();
+    itoa::fmt_grouped(&mut s, 1234567u32, "\u{2009}", 3).unwrap();
+    assert_eq!(s, "1\u{2009}234\u{2009}567");
+    assert_eq!(s.replace("\u{2009}", "").parse::<u32>(), Ok(1234567));
+
+    for &n in &[
+        0i64,
+        999,
+        -1000,
+        123456,
+        <i64>::min_value(),
+        <i64>::max_value(),
+    ] {
+        let mut s = String::new();
+        itoa::fmt_grouped(&mut s, n, "\u{a0}", 3).unwrap();
+        assert!(std::str::from_utf8(s.as_bytes()).is_ok());
+        assert_eq!(s.replace("\u{a0}", "").parse::<i64>(), Ok(n));
+
+        let mut buffer = itoa::WideBuffer::new();
+        assert_eq!(s.replace("\u{a0}", ","), buffer.format_grouped(n, b',', 3));
+    }
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_format_radix_grouped() {
+    let mut s = String::new();
+    itoa::format_radix_grouped(&mut s, 0xDEADBEEF, 16, 4, '_').unwrap();
+    assert_eq!(s, "dead_beef");
+
+    let mut s = String::new();
+    itoa::format_radix_grouped(&mut s, 1234567, 10, 3, '\u{2009}').unwrap();
+    assert_eq!(s, "1\u{2009}234\u{2009}567");
+
+    let mut s = String::new();
+    itoa::format_radix_grouped(&mut s, 0, 2, 4, '_').unwrap();
+    assert_eq!(s, "0");
+
+    for &(n, radix, group_size) in &[
+        (0xDEADBEEFu128, 16, 4),
+        (1234567, 10, 3),
+        (0b1011_0110, 2, 4),
+        (<u128>::max_value(), 36, 5),
+        (<u128>::max_value(), 8, 1),
+    ] {
+        let mut s = String::new();
+        itoa::format_radix_grouped(&mut s, n, radix, group_size, '_').unwrap();
+        assert_eq!(u128::from_str_radix(&s.replace('_', ""), radix), Ok(n));
+        for group in s.split('_').skip(1) {
+            assert_eq!(group.len(), group_size);
+        }
+    }
+}
+
+#[cfg(feature = "i128")]
+#[test]
+#[should_panic]
+fn test_format_radix_grouped_invalid_radix() {
+    let _ = itoa::format_radix_grouped(&mut String::new(), 1, 37, 3, '_');
+}
+
+#[test]
+fn test_format_rust_literal() {
+    let mut s = String::new();
+    itoa::format_rust_literal(&mut s, <u64>::max_value()).unwrap();
+    assert_eq!(s, "18_446_744_073_709_551_615");
+    assert_eq!(18_446_744_073_709_551_615, <u64>::max_value());
+
+    let mut s = String::new();
+    itoa::format_rust_literal(&mut s, 999).unwrap();
+    assert_eq!(s, "999");
+
+    for &n in &[0i64, -5, -1000, 123456, <i64>::min_value()] {
+        let mut s = String::new();
+        itoa::format_rust_literal(&mut s, n).unwrap();
+        assert_eq!(s.replace('_', "").parse::<i64>(), Ok(n));
+        assert!(!s.trim_start_matches('-').starts_with('_'));
+    }
+}
+
+#[test]
+fn test_join_into() {
+    let mut s = String::new();
+    itoa::join_into(&mut s, &[] as &[i32], ",").unwrap();
+    assert_eq!(s, "");
+
+    let mut s = String::new();
+    itoa::join_into(&mut s, &[-7i8], ",").unwrap();
+    assert_eq!(s, "-7");
+
+    let mut s = String::from("row: ");
+    itoa::join_into(&mut s, &[0i64, -1, 42, <i64>::min_value()], ", ").unwrap();
+    assert_eq!(s, "row: 0, -1, 42, -9223372036854775808");
+}
+
+#[test]
+fn test_format_with_digits() {
+    const FULLWIDTH: [&str; 10] = [
+        "\u{ff10}", "\u{ff11}", "\u{ff12}", "\u{ff13}", "\u{ff14}", "\u{ff15}", "\u{ff16}",
+        "\u{ff17}", "\u{ff18}", "\u{ff19}",
+    ];
+    const DEVANAGARI: [&str; 10] = [
+        "\u{966}", "\u{967}", "\u{968}", "\u{969}", "\u{96a}", "\u{96b}", "\u{96c}", "\u{96d}",
+        "\u{96e}", "\u{96f}",
+    ];
+
+    let mut s = String::new();
+    itoa::format_with_digits(&mut s, 1234567890u32, &FULLWIDTH, "\u{ff0d}").unwrap();
+    assert_eq!(
+        s,
+        "\u{ff11}\u{ff12}\u{ff13}\u{ff14}\u{ff15}\u{ff16}\u{ff17}\u{ff18}\u{ff19}\u{ff10}"
+    );
+
+    let mut s = String::new();
+    itoa::format_with_digits(&mut s, -305i16, &FULLWIDTH, "\u{ff0d}").unwrap();
+    assert_eq!(s, "\u{ff0d}\u{ff13}\u{ff10}\u{ff15}");
+
+    let mut s = String::new();
+    itoa::format_with_digits(&mut s, -2024i32, &DEVANAGARI, "-").unwrap();
+    assert_eq!(s, "-\u{968}\u{966}\u{968}\u{96a}");
+
+    let mut s = String::new();
+    itoa::format_with_digits(&mut s, 0u8, &DEVANAGARI, "-").unwrap();
+    assert_eq!(s, "\u{966}");
+}
+
+#[test]
+fn test_format_superscript() {
+    let mut s = String::new();
+    itoa::format_superscript(&mut s, 123).unwrap();
+    assert_eq!(s, "\u{b9}\u{b2}\u{b3}");
+
+    let mut s = String::new();
+    itoa::format_superscript(&mut s, -4567890).unwrap();
+    assert_eq!(
+        s,
+        "\u{207b}\u{2074}\u{2075}\u{2076}\u{2077}\u{2078}\u{2079}\u{2070}"
+    );
+
+    let mut s = String::new();
+    itoa::format_subscript(&mut s, -1234567890).unwrap();
+    assert_eq!(
+        s,
+        "\u{208b}\u{2081}\u{2082}\u{2083}\u{2084}\u{2085}\u{2086}\u{2087}\u{2088}\u{2089}\u{2080}"
+    );
+}
+
+#[test]
+fn test_fmt_with_spec() {
+    use std::fmt;
+
+    struct Id(i32);
+
+    impl fmt::Display for Id {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            itoa::fmt_with_spec(f, self.0)
+        }
+    }
+
+    assert_eq!(format!("{:+05}", Id(42)), "+0042");
+    assert_eq!(format!("{:+05}", Id(-42)), "-0042");
+    assert_eq!(format!("{:>10}", Id(-42)), "       -42");
+    assert_eq!(format!("{:<10}|", Id(42)), "42        |");
+    assert_eq!(format!("{:_^9}", Id(-1)), "___-1____");
+    assert_eq!(format!("{}", Id(<i32>::min_value())), "-2147483648");
+}
+
+#[cfg(not(no_const_loop))]
+#[test]
+fn test_const_format_u64() {
+    const ZERO: ([u8; 20], usize) = itoa::const_format_u64(0);
+    const MAX: ([u8; 20], usize) = itoa::const_format_u64(<u64>::max_value());
+
+    assert_eq!(&ZERO.0[..ZERO.1], b"0");
+    assert_eq!(&MAX.0[..MAX.1], &b"18446744073709551615"[..]);
+
+    let mut buffer = itoa::Buffer::new();
+    for &n in &[1u64, 9, 10, 99, 100, 12345, 1 << 32, 1 << 63] {
+        let (bytes, len) = itoa::const_format_u64(n);
+        assert_eq!(&bytes[..len], buffer.format(n).as_bytes());
+    }
+}
+
+#[test]
+fn test_last() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.last(), None);
+
+    let printed = buffer.format(-1234i32).to_owned();
+    assert_eq!(buffer.last(), Some(&*printed));
+
+    buffer.format_abs(-1234567i32);
+    assert_eq!(buffer.last(), Some("1234567"));
+    buffer.format_detailed(5u8);
+    assert_eq!(buffer.last(), Some("5"));
+    buffer.format_padded(7u8, 3);
+    assert_eq!(buffer.last(), Some("007"));
+
+    assert_eq!(buffer.clone().last(), None);
+    let copy = buffer;
+    assert_eq!(copy.last(), Some("007"));
+    assert_eq!(itoa::Buffer::default().last(), None);
+}
+
+#[test]
+#[allow(clippy::clone_on_copy)]
+fn test_buffer_eq_hash() {
+    use std::collections::HashSet;
+
+    assert!(itoa::Buffer::new() == itoa::Buffer::new());
+
+    let mut a = itoa::Buffer::new();
+    let mut b = itoa::Buffer::new();
+    a.format(42u8);
+    assert!(a != b);
+    b.format_padded(42i64, 0);
+    assert!(a == b);
+    assert!(a.clone() != a);
+    assert!(a.clone() == itoa::Buffer::new());
+    b.format(-42);
+    assert!(a != b);
+
+    let mut set = HashSet::new();
+    for &n in &[1, 2, 1, 3, 2] {
+        let mut buffer = itoa::Buffer::new();
+        buffer.format(n);
+        set.insert(buffer);
+    }
+    set.insert(itoa::Buffer::new());
+    set.insert(itoa::Buffer::new());
+    assert_eq!(set.len(), 4);
+}
+
+#[test]
+fn test_buffer_display() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(format!("{}", buffer), "");
+    assert_eq!(format!("{:?}", buffer), "Buffer(<uninit>)");
+
+    buffer.format(-1234i32);
+    assert_eq!(format!("{}", buffer), "-1234");
+    assert_eq!(format!("{:?}", buffer), "Buffer(\"-1234\")");
+}
+
+#[test]
+fn test_buffer_size() {
+    // Base 10 output only needs room for i128::MIN.
+    assert_eq!(std::mem::size_of::<itoa::Buffer>(), 42);
+    assert_eq!(std::mem::size_of::<itoa::WideBuffer>(), 130);
+}
+
+#[test]
+fn test_capacity_is_empty() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.capacity(), 40);
+    assert!(buffer.is_empty());
+    buffer.format(0u8);
+    assert!(!buffer.is_empty());
+    buffer.format_appending(1u8);
+    assert!(!buffer.is_empty());
+    buffer.reset();
+    assert!(buffer.is_empty());
+    assert!(buffer.clone().is_empty());
+}
+
+#[cfg(feature = "i128")]
+#[test]
+fn test_capacity_i128() {
+    let mut buffer = itoa::Buffer::new();
+    let capacity = buffer.capacity();
+    assert_eq!(capacity, <i128 as itoa::Integer>::MAX_STR_LEN);
+    let s = buffer.format(<i128>::min_value());
+    assert_eq!(capacity, s.len());
+}
+
+#[test]
+fn test_format_appending() {
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.format_appending(1u8), Some("1"));
+    assert_eq!(buffer.format_appending(-23i16), Some("1-23"));
+    assert_eq!(buffer.format_appending(456u64), Some("1-23456"));
+
+    buffer.reset();
+    assert_eq!(buffer.format_appending(7i32), Some("7"));
+
+    // A regular format discards the appended content.
+    buffer.format(99);
+    assert_eq!(buffer.format_appending(0), Some("0"));
+}
+
+#[test]
+fn test_format_appending_exhausted() {
+    let mut buffer = itoa::Buffer::new();
+    let mut expected = String::new();
+    while let Some(s) = buffer.format_appending(<i64>::min_value()) {
+        expected.push_str("-9223372036854775808");
+        assert_eq!(s, expected);
+    }
+    assert_eq!(expected.len(), 40);
+
+    // Rejected values leave the content intact.
+    assert_eq!(buffer.format_appending(1), None);
+    assert_eq!(buffer.as_str(), expected);
+    assert_eq!(buffer.format_appending(0), None);
+    assert_eq!(buffer.last(), None);
+}
+
+#[test]
+fn test_buffer_fmt_write() {
+    use std::fmt::Write;
+
+    let mut buffer = itoa::Buffer::new();
+    assert_eq!(buffer.as_str(), "");
+    write!(buffer, "[{}, {}]", 42, -7i8).unwrap();
+    assert_eq!(buffer.as_str(), "[42, -7]");
+    assert_eq!(buffer.format_appending(1u8), Some("[42, -7]1"));
+    buffer.write_str("!").unwrap();
+    assert_eq!(buffer.as_str(), "[42, -7]1!");
+
+    buffer.reset();
+    assert_eq!(buffer.as_str(), "");
+    buffer.format(5);
+    assert_eq!(buffer.as_str(), "");
+
+    // A write that does not fit is rejected, keeping the earlier content.
+    let long = "x".repeat(20);
+    buffer.write_str(&long).unwrap();
+    assert!(write!(buffer, "{}{}", "y".repeat(10), long).is_err());
+    assert_eq!(buffer.as_str().len(), 30);
+    assert!(buffer.as_str().ends_with(&format!("x{}", "y".repeat(10))));
+    assert!(buffer.write_str("0123456789").is_ok());
+    assert!(buffer.write_str("z").is_err());
+    assert_eq!(buffer.as_str().len(), 40);
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn test_push_arraystring() {
+    let mut s = arrayvec::ArrayString::<8>::new();
+    assert!(itoa::push_arraystring(&mut s, -1234i32).is_ok());
+    assert!(itoa::push_arraystring(&mut s, 567u16).is_ok());
+    assert_eq!(&s[..], "-1234567");
+
+    let mut s = arrayvec::ArrayString::<5>::new();
+    s.push('x');
+    assert!(itoa::push_arraystring(&mut s, 12345u32).is_err());
+    assert_eq!(&s[..], "x");
+    assert!(itoa::push_arraystring(&mut s, 1234u32).is_ok());
+    assert_eq!(&s[..], "x1234");
+}